@@ -0,0 +1,259 @@
+//--------------------------------------------------------------------------------------------------
+// BENCHMARK OVERVIEW
+//--------------------------------------------------------------------------------------------------
+// Hot-path benchmarks for the matching engine and order book. Every scenario builds its book in
+// an untimed setup step, so only the measured operation is timed.
+//
+// | Group                | Description                                                      |
+// |----------------------|------------------------------------------------------------------|
+// | insert               | Passive limit order added to a populated book                    |
+// | cancel               | Cancel of an order resting mid-book                              |
+// | match_sweep          | Single aggressive order sweeping N price levels                  |
+// | depth_snapshot       | Aggregated top-N depth from a deep book                          |
+// | mixed_workload       | Seeded stream of passive/aggressive/market/cancel events         |
+//
+// Baselines: criterion writes JSON estimates under `target/criterion/<group>/<bench>/`.
+// Record one with `cargo bench --bench matching_bench -- --save-baseline main` and compare a
+// change against it with `cargo bench --bench matching_bench -- --baseline main`.
+//--------------------------------------------------------------------------------------------------
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ultimate_matching::matching_engine::MatchingEngine;
+use ultimate_matching::types::{Order, Side, OrderType, TimeInForce};
+use rust_decimal_macros::dec;
+use uuid::Uuid;
+use rust_decimal::Decimal;
+
+/// Number of price levels resting on each side for the insert/cancel/depth scenarios.
+const BOOK_LEVELS: u32 = 100;
+/// Mid price of the populated book; well above the deepest ladder so every bid stays positive.
+const BOOK_MID: u32 = 1_000;
+/// Number of events replayed per iteration of the mixed workload.
+const WORKLOAD_EVENTS: usize = 10_000;
+/// Fixed seed so every run replays the same order flow.
+const WORKLOAD_SEED: u64 = 42;
+
+fn create_test_order(side: Side, order_type: OrderType, price: Option<Decimal>, quantity: Decimal, instrument_id: Uuid) -> Order {
+    let mut builder = Order::builder()
+        .account_id(Uuid::new_v4())
+        .instrument_id(instrument_id)
+        .side(side)
+        .order_type(order_type)
+        .base_amount(quantity);
+    if let Some(price) = price {
+        builder = builder.limit_price(price);
+    }
+    builder.build().expect("bench order is valid")
+}
+
+/// Builds an engine with `levels` bid levels below `BOOK_MID` and `levels` ask levels above it,
+/// one order of size 1 per level. Returns the engine and the IDs of the resting bids.
+fn populated_engine(instrument_id: Uuid, levels: u32) -> (MatchingEngine, Vec<Uuid>) {
+    assert!(levels < BOOK_MID, "{} levels would push bids to non-positive prices", levels);
+    let mid = Decimal::from(BOOK_MID);
+    let mut engine = MatchingEngine::new(instrument_id);
+    let mut bid_ids = Vec::with_capacity(levels as usize);
+    for i in 1..=levels {
+        let bid = create_test_order(Side::Bid, OrderType::Limit, Some(mid - Decimal::from(i)), dec!(1), instrument_id);
+        bid_ids.push(bid.id);
+        engine.process_order(bid, TimeInForce::GTC).expect("bid rests");
+        let ask = create_test_order(Side::Ask, OrderType::Limit, Some(mid + Decimal::from(i)), dec!(1), instrument_id);
+        engine.process_order(ask, TimeInForce::GTC).expect("ask rests");
+    }
+    (engine, bid_ids)
+}
+
+fn insert_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+    let instrument_id = Uuid::new_v4();
+
+    group.bench_function("passive_limit", |b| {
+        b.iter_batched(
+            || {
+                let (engine, _) = populated_engine(instrument_id, BOOK_LEVELS);
+                // Lands between two bid levels in the middle of the ladder
+                let order = create_test_order(Side::Bid, OrderType::Limit, Some(Decimal::from(BOOK_MID) - dec!(49.5)), dec!(1), instrument_id);
+                (engine, order)
+            },
+            |(mut engine, order)| {
+                let _ = black_box(engine.process_order(order, TimeInForce::GTC));
+                engine
+            },
+            BatchSize::SmallInput,
+        );
+    });
+
+    group.finish();
+}
+
+fn cancel_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cancel");
+    let instrument_id = Uuid::new_v4();
+
+    group.bench_function("mid_book", |b| {
+        b.iter_batched(
+            || {
+                let (engine, bid_ids) = populated_engine(instrument_id, BOOK_LEVELS);
+                let target = bid_ids[bid_ids.len() / 2];
+                (engine, target)
+            },
+            |(mut engine, target)| {
+                let _ = black_box(engine.cancel_order(target));
+                engine
+            },
+            BatchSize::SmallInput,
+        );
+    });
+
+    group.finish();
+}
+
+fn match_sweep_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("match_sweep");
+    let instrument_id = Uuid::new_v4();
+
+    for levels in [1u32, 10, 100] {
+        group.throughput(Throughput::Elements(u64::from(levels)));
+        group.bench_with_input(BenchmarkId::from_parameter(levels), &levels, |b, &levels| {
+            b.iter_batched(
+                || {
+                    let (engine, _) = populated_engine(instrument_id, levels);
+                    // Crosses every ask level exactly once
+                    let taker = create_test_order(
+                        Side::Bid,
+                        OrderType::Limit,
+                        Some(Decimal::from(BOOK_MID + levels)),
+                        Decimal::from(levels),
+                        instrument_id,
+                    );
+                    (engine, taker)
+                },
+                |(mut engine, taker)| {
+                    let _ = black_box(engine.process_order(taker, TimeInForce::IOC));
+                    engine
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+fn depth_snapshot_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("depth_snapshot");
+    let instrument_id = Uuid::new_v4();
+    let (engine, _) = populated_engine(instrument_id, 5 * BOOK_LEVELS);
+
+    for levels in [10usize, 100] {
+        group.bench_with_input(BenchmarkId::from_parameter(levels), &levels, |b, &levels| {
+            b.iter(|| black_box(engine.order_book().depth(black_box(levels))));
+        });
+    }
+
+    group.finish();
+}
+
+/// A single pre-generated event of the mixed workload.
+// Boxing the order would put an extra allocation and free inside the timed loop
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+enum WorkloadEvent {
+    Place(Order, TimeInForce),
+    Cancel(Uuid),
+}
+
+/// Generates a reproducible order stream around a mid price of 100.
+///
+/// Each event type is an independent Poisson process; merging them is equivalent to drawing the
+/// next event type with probability proportional to its rate, which is what this does. Only the
+/// sequence of events matters to the engine, so arrival times themselves are not materialized.
+fn generate_workload(instrument_id: Uuid, events: usize, seed: u64) -> Vec<WorkloadEvent> {
+    // Relative arrival rates: passive limits, aggressive limits, market orders, cancels
+    const RATES: [u32; 4] = [60, 10, 5, 25];
+    let total_rate: u32 = RATES.iter().sum();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut resting_ids: Vec<Uuid> = Vec::new();
+    let mut workload = Vec::with_capacity(events);
+
+    while workload.len() < events {
+        let side = if rng.gen_bool(0.5) { Side::Bid } else { Side::Ask };
+        let direction = match side {
+            Side::Bid => dec!(-1),
+            Side::Ask => dec!(1),
+        };
+        let quantity = Decimal::from(rng.gen_range(1u32..=5));
+        let offset = Decimal::new(rng.gen_range(1i64..=40), 1);
+
+        let mut draw = rng.gen_range(0..total_rate);
+        let kind = RATES.iter().position(|&rate| {
+            if draw < rate {
+                true
+            } else {
+                draw -= rate;
+                false
+            }
+        });
+
+        let event = match kind {
+            Some(0) => {
+                let order = create_test_order(side, OrderType::Limit, Some(dec!(100) + direction * offset), quantity, instrument_id);
+                resting_ids.push(order.id);
+                WorkloadEvent::Place(order, TimeInForce::GTC)
+            }
+            Some(1) => {
+                let order = create_test_order(side, OrderType::Limit, Some(dec!(100) - direction * offset), quantity, instrument_id);
+                WorkloadEvent::Place(order, TimeInForce::IOC)
+            }
+            Some(2) => WorkloadEvent::Place(create_test_order(side, OrderType::Market, None, quantity, instrument_id), TimeInForce::IOC),
+            _ if resting_ids.is_empty() => continue,
+            _ => {
+                let index = rng.gen_range(0..resting_ids.len());
+                WorkloadEvent::Cancel(resting_ids.swap_remove(index))
+            }
+        };
+        workload.push(event);
+    }
+
+    workload
+}
+
+fn mixed_workload_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mixed_workload");
+    let instrument_id = Uuid::new_v4();
+    let workload = generate_workload(instrument_id, WORKLOAD_EVENTS, WORKLOAD_SEED);
+
+    group.throughput(Throughput::Elements(WORKLOAD_EVENTS as u64));
+    group.sample_size(20);
+    group.bench_function("poisson_mix", |b| {
+        b.iter_batched(
+            || (MatchingEngine::new(instrument_id), workload.clone()),
+            |(mut engine, events)| {
+                for event in events {
+                    // Rejections (e.g. market orders into an empty side) are part of the workload
+                    let _ = match event {
+                        WorkloadEvent::Place(order, tif) => engine.process_order(order, tif).map(|_| ()),
+                        WorkloadEvent::Cancel(id) => engine.cancel_order(id).map(|_| ()),
+                    };
+                }
+                engine
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    insert_benchmark,
+    cancel_benchmark,
+    match_sweep_benchmark,
+    depth_snapshot_benchmark,
+    mixed_workload_benchmark
+);
+criterion_main!(benches);
@@ -1,36 +1,22 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use ultimate_matching::orderbook::OrderBook;
 use ultimate_matching::matching_engine::MatchingEngine;
-use ultimate_matching::types::{Order, Side, OrderType, OrderStatus, CreatedFrom, TimeInForce};
+use ultimate_matching::types::{Order, Side, OrderType, TimeInForce};
 use rust_decimal_macros::dec;
 use uuid::Uuid;
-use chrono::Utc;
 use rust_decimal::Decimal;
 
 fn create_test_order(side: Side, price: Decimal, quantity: Decimal, instrument_id: Uuid) -> Order {
-    let now = Utc::now();
-    Order {
-        id: Uuid::new_v4(),
-        ext_id: Some("bench-order".to_string()),
-        account_id: Uuid::new_v4(),
-        order_type: OrderType::Limit,
-        instrument_id,
-        side,
-        limit_price: Some(price),
-        trigger_price: None,
-        base_amount: quantity,
-        remaining_base: quantity,
-        filled_quote: dec!(0.0),
-        filled_base: dec!(0.0),
-        remaining_quote: price * quantity,
-        expiration_date: now + chrono::Duration::days(365),
-        status: OrderStatus::New,
-        created_at: now,
-        updated_at: now,
-        trigger_by: None,
-        created_from: CreatedFrom::Api,
-        sequence_id: 1,
-    }
+    Order::builder()
+        .ext_id("bench-order")
+        .account_id(Uuid::new_v4())
+        .instrument_id(instrument_id)
+        .side(side)
+        .order_type(OrderType::Limit)
+        .limit_price(price)
+        .base_amount(quantity)
+        .build()
+        .expect("bench order is valid")
 }
 
 fn orderbook_benchmark(c: &mut Criterion) {
@@ -0,0 +1,143 @@
+//--------------------------------------------------------------------------------------------------
+// REPLAY BINARY
+//--------------------------------------------------------------------------------------------------
+// Replays a CSV of historical orders through a fresh MatchingEngine and verifies the resulting
+// trades against a recorded trade log. See `ultimate_matching::replay` for the CSV formats.
+//
+// Usage:
+//   replay <orders.csv> [--expect <trades.csv>] [--write-trades <out.csv>] [--speed <factor>]
+//
+// | Option          | Description                                                            |
+// |-----------------|------------------------------------------------------------------------|
+// | --expect        | Recorded trades to verify against; exits with 1 on the first mismatch  |
+// | --write-trades  | Writes the replayed trades, e.g. to record a new baseline              |
+// | --speed         | Real-time pacing factor (1 = original gaps); as fast as possible if    |
+// |                 | omitted                                                                |
+// | --instrument    | Instrument ID for the engine; a random one if omitted                  |
+//--------------------------------------------------------------------------------------------------
+
+use std::fs;
+use std::process::ExitCode;
+use std::time::Instant;
+use uuid::Uuid;
+
+use ultimate_matching::replay::{self, ReplaySpeed};
+
+/// Global allocator when built with `--features jemalloc`, matching the main binary.
+#[cfg(feature = "jemalloc")]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+/// Global allocator when built with `--features mimalloc`, matching the main binary.
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+const USAGE: &str = "usage: replay <orders.csv> [--expect <trades.csv>] [--write-trades <out.csv>] [--speed <factor>] [--instrument <uuid>]";
+const TRADES_HEADER: &str = "taker_order_id,maker_order_id,price,base_amount";
+
+/// Parsed command line options.
+struct Options {
+    orders_path: String,
+    expect_path: Option<String>,
+    write_trades_path: Option<String>,
+    speed: ReplaySpeed,
+    instrument_id: Uuid,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut orders_path = None;
+    let mut expect_path = None;
+    let mut write_trades_path = None;
+    let mut speed = ReplaySpeed::AsFastAsPossible;
+    let mut instrument_id = Uuid::new_v4();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} requires a value", name));
+        match arg.as_str() {
+            "--expect" => expect_path = Some(value("--expect")?),
+            "--write-trades" => write_trades_path = Some(value("--write-trades")?),
+            "--speed" => {
+                let factor = value("--speed")?.parse().map_err(|_| "--speed must be a number".to_string())?;
+                speed = ReplaySpeed::RealTime(factor);
+            }
+            "--instrument" => {
+                instrument_id = value("--instrument")?.parse().map_err(|_| "--instrument must be a UUID".to_string())?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ if orders_path.is_none() => orders_path = Some(arg),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    Ok(Options {
+        orders_path: orders_path.ok_or("missing <orders.csv>")?,
+        expect_path,
+        write_trades_path,
+        speed,
+        instrument_id,
+    })
+}
+
+fn run(options: Options) -> Result<bool, String> {
+    let read = |path: &str| fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e));
+
+    let events = replay::parse_orders_csv(&read(&options.orders_path)?, options.instrument_id)
+        .map_err(|e| format!("{}: {}", options.orders_path, e))?;
+    let recorded = match &options.expect_path {
+        Some(path) => Some(replay::parse_trades_csv(&read(path)?).map_err(|e| format!("{}: {}", path, e))?),
+        None => None,
+    };
+
+    let started = Instant::now();
+    let report = replay::replay(events, options.instrument_id, options.speed, recorded.as_deref()).map_err(|e| e.to_string())?;
+    let elapsed = started.elapsed();
+
+    println!(
+        "replayed {} events ({} rejected) into {} trades in {:.3}s",
+        report.events,
+        report.rejected,
+        report.trades.len(),
+        elapsed.as_secs_f64()
+    );
+
+    if let Some(path) = &options.write_trades_path {
+        let mut output = String::from(TRADES_HEADER);
+        for trade in &report.trades {
+            output.push('\n');
+            output.push_str(&trade.to_csv_row());
+        }
+        output.push('\n');
+        fs::write(path, output).map_err(|e| format!("{}: {}", path, e))?;
+    }
+
+    if let Some(mismatch) = &report.mismatch {
+        let describe = |trade: Option<replay::RecordedTrade>| trade.map_or("<none>".to_string(), |t| t.to_csv_row());
+        eprintln!("trade #{} diverged", mismatch.index);
+        eprintln!("  expected: {}", describe(mismatch.expected));
+        eprintln!("  actual:   {}", describe(mismatch.actual));
+    } else if recorded.is_some() {
+        println!("trades match the recorded log");
+    }
+
+    Ok(report.is_consistent())
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(options) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::from(2)
+        }
+    }
+}
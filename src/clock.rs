@@ -0,0 +1,87 @@
+//--------------------------------------------------------------------------------------------------
+// MODULE OVERVIEW
+//--------------------------------------------------------------------------------------------------
+// This module abstracts the wall clock the engine stamps trades and results with, so tests and
+// replays can run against a controlled time instead of `Utc::now()`.
+//
+// | Component     | Description                                                               |
+// |---------------|---------------------------------------------------------------------------|
+// | TimeSource    | Source of wall-clock timestamps                                           |
+// | SystemClock   | Reads the system clock; the engine default                                |
+// | ManualClock   | Shared, manually advanced clock for tests and replays                     |
+//
+//--------------------------------------------------------------------------------------------------
+// TESTS
+//--------------------------------------------------------------------------------------------------
+// | Name                                | Description                                         |
+// |-------------------------------------|-----------------------------------------------------|
+// | test_manual_clock_is_shared         | Clones observe set/advance from any handle          |
+//--------------------------------------------------------------------------------------------------
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+
+/// Source of wall-clock timestamps for the engine.
+pub trait TimeSource: fmt::Debug + Send {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Reads the system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so a test can keep one
+/// handle while the engine owns another.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl ManualClock {
+    /// Creates a clock stopped at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { now: Arc::new(Mutex::new(start)) }
+    }
+
+    /// Moves the clock to `now`.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+}
+
+impl TimeSource for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_is_shared() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let handle = clock.clone();
+
+        handle.advance(Duration::milliseconds(250));
+        assert_eq!(clock.now(), start + Duration::milliseconds(250));
+
+        clock.set(start);
+        assert_eq!(handle.now(), start);
+    }
+}
@@ -0,0 +1,245 @@
+//--------------------------------------------------------------------------------------------------
+// MODULE OVERVIEW
+//--------------------------------------------------------------------------------------------------
+// This module maintains per-account positions from executed fills. Each (account, instrument)
+// pair tracks a signed base position, quote cash flow, average entry price and realized PnL,
+// with unrealized PnL computed on demand against a mark price.
+//
+// | Component     | Description                                                               |
+// |---------------|---------------------------------------------------------------------------|
+// | Ledger        | Position store fed by MatchResults, trades or individual fills            |
+// | Position      | Balances and PnL for one account on one instrument                        |
+//
+//--------------------------------------------------------------------------------------------------
+// STRUCTS
+//--------------------------------------------------------------------------------------------------
+// | Name          | Description                                        | Key Methods              |
+// |---------------|----------------------------------------------------|--------------------------|
+// | Position      | Signed base, quote flow, avg entry, realized PnL   | unrealized_pnl           |
+// |               |                                                    | is_flat                  |
+// |---------------|----------------------------------------------------|--------------------------|
+// | Ledger        | Positions keyed by (account, instrument)           | apply_match_result       |
+// |               |                                                    | apply_trade              |
+// |               |                                                    | apply_fill               |
+// |               |                                                    | position                 |
+// |               |                                                    | positions_for_account    |
+//
+//--------------------------------------------------------------------------------------------------
+// TESTS
+//--------------------------------------------------------------------------------------------------
+// | Name                                  | Description                                       |
+// |---------------------------------------|---------------------------------------------------|
+// | test_open_and_add_averages_entry      | Adding to a position averages the entry price     |
+// | test_partial_close_realizes_pnl       | Reducing a position realizes PnL at avg entry     |
+// | test_flip_resets_entry_price          | Crossing through flat reopens at the fill price   |
+// | test_apply_match_result_both_sides    | Taker and makers are booked on opposite sides     |
+//--------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::matching_engine::MatchResult;
+use crate::types::{Side, Trade};
+
+/// Balances and PnL for one account on one instrument.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    /// Net base position: positive when long, negative when short.
+    pub base_balance: Decimal,
+    /// Net quote cash flow: negative after buying, positive after selling.
+    pub quote_balance: Decimal,
+    /// Average price of the open position; zero when flat.
+    pub average_entry_price: Decimal,
+    /// PnL locked in by reducing or closing the position.
+    pub realized_pnl: Decimal,
+}
+
+impl Position {
+    /// Returns the PnL of the open position if it were closed at `mark_price`.
+    pub fn unrealized_pnl(&self, mark_price: Decimal) -> Decimal {
+        (mark_price - self.average_entry_price) * self.base_balance
+    }
+
+    /// Returns true if there is no open base position.
+    pub fn is_flat(&self) -> bool {
+        self.base_balance.is_zero()
+    }
+
+    /// Books a fill of `base_amount` at `price` on `side`.
+    fn apply(&mut self, side: Side, base_amount: Decimal, price: Decimal) {
+        let signed_amount = match side {
+            Side::Bid => base_amount,
+            Side::Ask => -base_amount,
+        };
+        self.quote_balance -= signed_amount * price;
+
+        let new_balance = self.base_balance + signed_amount;
+        let is_increasing = self.base_balance.is_zero()
+            || self.base_balance.is_sign_positive() == signed_amount.is_sign_positive();
+
+        if is_increasing {
+            // Weighted average of the existing position and the new fill
+            self.average_entry_price = (self.average_entry_price * self.base_balance.abs() + price * base_amount)
+                / new_balance.abs();
+        } else {
+            let closed = Decimal::min(base_amount, self.base_balance.abs());
+            let direction = if self.base_balance.is_sign_positive() { Decimal::ONE } else { Decimal::NEGATIVE_ONE };
+            self.realized_pnl += (price - self.average_entry_price) * closed * direction;
+
+            if new_balance.is_zero() {
+                self.average_entry_price = Decimal::ZERO;
+            } else if new_balance.is_sign_positive() != self.base_balance.is_sign_positive() {
+                // Flipped through flat: the remainder opens a fresh position at this price
+                self.average_entry_price = price;
+            }
+        }
+        self.base_balance = new_balance;
+    }
+}
+
+/// Per-account position store fed by executed fills.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    /// Positions keyed by (account_id, instrument_id)
+    positions: HashMap<(Uuid, Uuid), Position>,
+}
+
+impl Ledger {
+    /// Creates an empty ledger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Books every trade in `result` for both the taker and the maker.
+    ///
+    /// # Arguments
+    /// * `result` - The outcome of `MatchingEngine::process_order` or `amend_order`
+    pub fn apply_match_result(&mut self, result: &MatchResult) {
+        for trade in &result.trades {
+            self.apply_trade(trade);
+        }
+    }
+
+    /// Books both sides of a single trade.
+    pub fn apply_trade(&mut self, trade: &Trade) {
+        self.apply_fill(trade.taker_account_id, trade.instrument_id, trade.taker_side, trade.base_amount, trade.price);
+        self.apply_fill(trade.maker_account_id, trade.instrument_id, trade.maker_side(), trade.base_amount, trade.price);
+    }
+
+    /// Books a single fill for one account.
+    ///
+    /// # Arguments
+    /// * `account_id` - The account that traded
+    /// * `instrument_id` - The instrument traded
+    /// * `side` - The side the account traded on
+    /// * `base_amount` - Quantity filled in base units
+    /// * `price` - Price of the fill
+    pub fn apply_fill(&mut self, account_id: Uuid, instrument_id: Uuid, side: Side, base_amount: Decimal, price: Decimal) {
+        self.positions
+            .entry((account_id, instrument_id))
+            .or_default()
+            .apply(side, base_amount, price);
+    }
+
+    /// Returns the position of `account_id` on `instrument_id`, if it has ever traded it.
+    pub fn position(&self, account_id: Uuid, instrument_id: Uuid) -> Option<&Position> {
+        self.positions.get(&(account_id, instrument_id))
+    }
+
+    /// Returns every (instrument_id, position) pair held by `account_id`.
+    pub fn positions_for_account(&self, account_id: Uuid) -> impl Iterator<Item = (Uuid, &Position)> {
+        self.positions
+            .iter()
+            .filter(move |((account, _), _)| *account == account_id)
+            .map(|((_, instrument), position)| (*instrument, position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use crate::matching_engine::MatchingEngine;
+    use crate::types::{Order, OrderType, TimeInForce};
+
+    fn create_test_order(side: Side, price: Decimal, quantity: Decimal, instrument_id: Uuid) -> Order {
+        Order::builder()
+            .account_id(Uuid::new_v4())
+            .instrument_id(instrument_id)
+            .side(side)
+            .order_type(OrderType::Limit)
+            .limit_price(price)
+            .base_amount(quantity)
+            .build()
+            .expect("test order is valid")
+    }
+
+    #[test]
+    fn test_open_and_add_averages_entry() {
+        let mut position = Position::default();
+        position.apply(Side::Bid, dec!(1), dec!(100));
+        position.apply(Side::Bid, dec!(3), dec!(104));
+
+        assert_eq!(position.base_balance, dec!(4));
+        assert_eq!(position.quote_balance, dec!(-412));
+        assert_eq!(position.average_entry_price, dec!(103));
+        assert_eq!(position.unrealized_pnl(dec!(105)), dec!(8));
+        assert_eq!(position.realized_pnl, dec!(0));
+    }
+
+    #[test]
+    fn test_partial_close_realizes_pnl() {
+        let mut position = Position::default();
+        position.apply(Side::Ask, dec!(2), dec!(100));
+        position.apply(Side::Bid, dec!(1), dec!(90));
+
+        // Short covered 10 below entry
+        assert_eq!(position.base_balance, dec!(-1));
+        assert_eq!(position.realized_pnl, dec!(10));
+        assert_eq!(position.average_entry_price, dec!(100));
+        assert_eq!(position.unrealized_pnl(dec!(95)), dec!(5));
+
+        position.apply(Side::Bid, dec!(1), dec!(110));
+        assert!(position.is_flat());
+        assert_eq!(position.realized_pnl, dec!(0));
+        assert_eq!(position.average_entry_price, dec!(0));
+        assert_eq!(position.quote_balance, dec!(0));
+    }
+
+    #[test]
+    fn test_flip_resets_entry_price() {
+        let mut position = Position::default();
+        position.apply(Side::Bid, dec!(1), dec!(100));
+        position.apply(Side::Ask, dec!(3), dec!(120));
+
+        assert_eq!(position.base_balance, dec!(-2));
+        assert_eq!(position.realized_pnl, dec!(20));
+        assert_eq!(position.average_entry_price, dec!(120));
+    }
+
+    #[test]
+    fn test_apply_match_result_both_sides() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        let mut ledger = Ledger::new();
+
+        let maker_a = create_test_order(Side::Ask, dec!(100), dec!(1), instrument_id);
+        let maker_b = create_test_order(Side::Ask, dec!(101), dec!(1), instrument_id);
+        let (maker_a_account, maker_b_account) = (maker_a.account_id, maker_b.account_id);
+        ledger.apply_match_result(&engine.process_order(maker_a, TimeInForce::GTC).unwrap());
+        ledger.apply_match_result(&engine.process_order(maker_b, TimeInForce::GTC).unwrap());
+        assert!(ledger.position(maker_a_account, instrument_id).is_none());
+
+        let taker = create_test_order(Side::Bid, dec!(101), dec!(2), instrument_id);
+        let taker_account = taker.account_id;
+        ledger.apply_match_result(&engine.process_order(taker, TimeInForce::IOC).unwrap());
+
+        let taker_position = ledger.position(taker_account, instrument_id).unwrap();
+        assert_eq!(taker_position.base_balance, dec!(2));
+        assert_eq!(taker_position.quote_balance, dec!(-201));
+        assert_eq!(ledger.position(maker_a_account, instrument_id).unwrap().base_balance, dec!(-1));
+        assert_eq!(ledger.position(maker_b_account, instrument_id).unwrap().quote_balance, dec!(101));
+        assert_eq!(ledger.positions_for_account(taker_account).count(), 1);
+    }
+}
@@ -1,9 +1,18 @@
 // Expose the modules
 pub mod types;
+pub mod clock;
 pub mod orderbook;
 pub mod matching_engine;
+pub mod risk;
+pub mod ledger;
+pub mod reference_price;
+pub mod replay;
 
 // Re-export key types for easier usage
-pub use types::{Order, Side, OrderType, OrderStatus, Trade, TimeInForce};
-pub use orderbook::OrderBook;
-pub use matching_engine::{MatchingEngine, MatchResult, MatchingError}; 
\ No newline at end of file
+pub use types::{Order, OrderBuilder, Side, OrderType, OrderStatus, Trade, Fill, TimeInForce, Liquidity};
+pub use clock::{TimeSource, SystemClock, ManualClock};
+pub use orderbook::{OrderBook, DepthLevel, DepthSnapshot};
+pub use matching_engine::{MatchingEngine, MatchResult, MatchingError, CrossingPolicy, CrossingAction};
+pub use risk::{RiskCheck, RiskPipeline, RiskLimits, LimitCheck, RateLimit, RateLimitCheck, ExposureCheck, AccountUsage, RiskRejection};
+pub use ledger::{Ledger, Position};
+pub use reference_price::ReferencePrices;
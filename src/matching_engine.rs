@@ -7,8 +7,9 @@
 // | Component                | Description                                                |
 // |--------------------------|-----------------------------------------------------------|
 // | MatchingEngine           | Main engine for processing and matching orders            |
-// | TimeInForce              | Order duration policy (GTC, IOC)                          |
+// | TimeInForce              | Order duration policy (GTC, IOC, GTX)                     |
 // | MatchResult              | Represents the outcome of a matching operation            |
+// | CrossingPolicy           | Handling of GTX orders that would cross on entry          |
 // | MatchingError            | Error types specific to the matching process              |
 //
 //--------------------------------------------------------------------------------------------------
@@ -19,39 +20,53 @@
 // | MatchingEngine          | Core matching engine                              | process_order    |
 // |                         |                                                   | match_order      |
 // |                         |                                                   | cancel_order     |
+// |                         |                                                   | amend_order      |
 // |-------------------------|---------------------------------------------------|------------------|
 // | MatchResult             | Result of a matching operation                    | trades           |
 // |                         |                                                   | processed_order  |
-// |                         |                                                   | affected_orders  |
+// |                         |                                                   | fills            |
+// |                         |                                                   | sequence_id      |
+// |                         |                                                   | timestamp        |
+// |                         |                                                   | crossing_action  |
 //
 //--------------------------------------------------------------------------------------------------
 // ENUMS
 //--------------------------------------------------------------------------------------------------
 // | Name                    | Description                                       | Variants         |
 // |-------------------------|---------------------------------------------------|------------------|
-// | TimeInForce             | Order duration policy                             | GTC, IOC         |
+// | TimeInForce             | Order duration policy                             | GTC, IOC, GTX    |
 // | MatchingError           | Errors that can occur during matching             | InvalidOrder     |
 // |                         |                                                   | OrderNotFound    |
 // |                         |                                                   | InsufficientLiq  |
+// |                         |                                                   | Type             |
+// | CrossingPolicy          | Handling of crossing GTX orders                   | Reject           |
+// |                         |                                                   | RepriceInside    |
+// |                         |                                                   | QueueAtTouch     |
+// | CrossingAction          | Outcome reported for a crossing GTX order         | Rejected         |
+// |                         |                                                   | Repriced         |
 //
 //--------------------------------------------------------------------------------------------------
 // FUNCTIONS
 //--------------------------------------------------------------------------------------------------
 // | Name                    | Description                                       | Return Type      |
 // |-------------------------|---------------------------------------------------|------------------|
+// | with_time_source        | Create an engine stamping time from a TimeSource  | MatchingEngine   |
+// | with_crossing_policy    | Set how crossing GTX orders are handled           | MatchingEngine   |
 // | process_order           | Process a new order                               | Result<MatchResu>|
 // | match_limit_order       | Match a limit order against the book              | Result<MatchResu>|
-// | cancel_order            | Cancel an existing order                          | Result<Order>    |
+// | cancel_order            | Cancel an existing order                          | Result<MatchResu>|
+// | amend_order             | Change price/quantity of a resting order          | Result<MatchResu>|
 //--------------------------------------------------------------------------------------------------
 
 use std::collections::HashMap;
 use rust_decimal::Decimal;
 use thiserror::Error;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
+use crate::clock::{SystemClock, TimeSource};
 use crate::orderbook::OrderBook;
-use crate::types::{Order, Side, OrderType, OrderStatus, Trade, TimeInForce};
+use crate::types::{Order, Side, OrderType, OrderStatus, Trade, Fill, TimeInForce, TypeError};
 
 /// Errors that can occur during the matching process.
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -67,11 +82,40 @@ pub enum MatchingError {
     /// There is insufficient liquidity to fill a market order.
     #[error("Insufficient liquidity to fill market order")]
     InsufficientLiquidity,
+
+    /// An order reached a state the order lifecycle does not allow.
+    #[error(transparent)]
+    Type(#[from] TypeError),
 }
 
 /// Type alias for Result with MatchingError
 pub type MatchingResult<T> = Result<T, MatchingError>;
 
+/// How the engine handles a GTX order that would cross the book on entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrossingPolicy {
+    /// Cancel the order.
+    #[default]
+    Reject,
+    /// Reprice the order one tick inside the opposite touch.
+    RepriceInside {
+        /// Minimum price increment of the instrument
+        tick_size: Decimal,
+    },
+    /// Reprice the order to the best price on its own side, joining the back of that queue.
+    /// Cancels the order if its own side is empty.
+    QueueAtTouch,
+}
+
+/// What the engine did with an order that would have crossed, as reported in its `MatchResult`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossingAction {
+    /// The order was cancelled.
+    Rejected,
+    /// The order was moved from its requested price to a passive one.
+    Repriced { from: Decimal, to: Decimal },
+}
+
 /// Represents the outcome of a matching operation.
 #[derive(Debug, Clone, Default)]
 pub struct MatchResult {
@@ -81,8 +125,17 @@ pub struct MatchResult {
     /// The order after processing (may be filled, partially filled, or cancelled)
     pub processed_order: Option<Order>,
     
-    /// Orders that were affected by this match (e.g., partially filled resting orders)
-    pub affected_orders: Vec<Order>,
+    /// Maker-side outcome of each trade, in the same order as `trades`
+    pub fills: Vec<Fill>,
+
+    /// Engine sequence number of the command that produced this result; strictly increasing
+    pub sequence_id: u64,
+
+    /// Wall-clock time the engine processed the command, from its `TimeSource`
+    pub timestamp: DateTime<Utc>,
+
+    /// Set when a GTX order would have crossed and the engine's `CrossingPolicy` was applied
+    pub crossing_action: Option<CrossingAction>,
 }
 
 /// The core matching engine responsible for processing orders and generating trades.
@@ -91,25 +144,91 @@ pub struct MatchingEngine {
     /// The order book for the instrument this engine is managing
     order_book: OrderBook,
     
-    /// Maps order IDs to their location (side, price) for fast cancellation
-    order_index: HashMap<Uuid, (Side, Decimal)>,
+    /// Maps order IDs to their location (side, price) for fast cancellation, and the time in
+    /// force they rest under so amendments keep honoring it
+    order_index: HashMap<Uuid, (Side, Decimal, TimeInForce)>,
     
     /// Sequence counter for assigning order priorities
     next_sequence_id: u64,
     
     /// Instrument ID this engine is managing
     instrument_id: Uuid,
+
+    /// Clock used to stamp trades, amendments and results
+    clock: Box<dyn TimeSource>,
+
+    /// Handling of GTX orders that would cross on entry
+    crossing_policy: CrossingPolicy,
 }
 
 impl MatchingEngine {
     /// Creates a new matching engine for a specific instrument.
     pub fn new(instrument_id: Uuid) -> Self {
+        Self::with_time_source(instrument_id, SystemClock)
+    }
+
+    /// Creates a matching engine that takes its wall-clock timestamps from `clock`.
+    pub fn with_time_source(instrument_id: Uuid, clock: impl TimeSource + 'static) -> Self {
         Self {
             order_book: OrderBook::new(instrument_id),
             order_index: HashMap::new(),
             next_sequence_id: 1,
             instrument_id,
+            clock: Box::new(clock),
+            crossing_policy: CrossingPolicy::default(),
+        }
+    }
+
+    /// Sets how GTX orders that would cross on entry are handled. Defaults to `Reject`.
+    pub fn with_crossing_policy(mut self, crossing_policy: CrossingPolicy) -> Self {
+        self.crossing_policy = crossing_policy;
+        self
+    }
+
+    /// Returns the engine's crossing policy.
+    pub fn crossing_policy(&self) -> CrossingPolicy {
+        self.crossing_policy
+    }
+
+    /// Returns the passive price the crossing policy moves a crossing order on `side` to, or
+    /// None if the order must be rejected.
+    fn passive_price(&self, side: Side) -> Option<Decimal> {
+        let price = match (self.crossing_policy, side) {
+            (CrossingPolicy::Reject, _) => None,
+            (CrossingPolicy::RepriceInside { tick_size }, Side::Bid) => self.order_book.best_ask().map(|ask| ask - tick_size),
+            (CrossingPolicy::RepriceInside { tick_size }, Side::Ask) => self.order_book.best_bid().map(|bid| bid + tick_size),
+            (CrossingPolicy::QueueAtTouch, Side::Bid) => self.order_book.best_bid(),
+            (CrossingPolicy::QueueAtTouch, Side::Ask) => self.order_book.best_ask(),
+        };
+        // Guards against a non-positive tick size producing a price that still crosses
+        price.filter(|&price| price > Decimal::ZERO && !self.order_book.would_cross(side, price))
+    }
+
+    /// Applies the crossing policy to a GTX `order` that would take liquidity at its limit price.
+    ///
+    /// # Returns
+    /// None if the order does not cross. Otherwise the action taken: the order is repriced in
+    /// place, or `Rejected` and left for the caller to cancel.
+    fn apply_crossing_policy(&self, order: &mut Order) -> Option<CrossingAction> {
+        let price = order.limit_price?;
+        if !self.order_book.would_cross(order.side, price) {
+            return None;
         }
+        Some(match self.passive_price(order.side) {
+            Some(passive_price) => {
+                order.limit_price = Some(passive_price);
+                order.remaining_quote = order.remaining_base * passive_price;
+                CrossingAction::Repriced { from: price, to: passive_price }
+            }
+            None => CrossingAction::Rejected,
+        })
+    }
+
+    /// Allocates the next engine sequence number.
+    fn next_sequence(&mut self) -> u64 {
+        let sequence_id = self.next_sequence_id;
+        self.next_sequence_id += 1;
+        sequence_id
     }
     
     /// Processes a new order through the matching engine.
@@ -128,10 +247,25 @@ impl MatchingEngine {
                         self.instrument_id, order.instrument_id)
             ));
         }
-        
-        // Assign sequence ID for time priority
-        order.sequence_id = self.next_sequence_id;
-        self.next_sequence_id += 1;
+
+        // Only fresh Limit/Market orders can be matched; conditional orders must trigger first.
+        // Checked up front because a failed status transition mid-match would strand fills.
+        if !matches!(order.order_type, OrderType::Limit | OrderType::Market) {
+            return Err(MatchingError::InvalidOrder(format!("{:?} orders cannot be matched directly", order.order_type)));
+        }
+        if order.status != OrderStatus::New {
+            return Err(MatchingError::InvalidOrder(format!("Order status must be New, got {:?}", order.status)));
+        }
+
+        // GTX orders must only ever add liquidity, which needs a limit price
+        if time_in_force == TimeInForce::GTX && order.order_type != OrderType::Limit {
+            return Err(MatchingError::InvalidOrder("GTX order must be a limit order".into()));
+        }
+
+        // Assign sequence ID for time priority and stamp the order with engine time
+        order.sequence_id = self.next_sequence();
+        let timestamp = self.clock.now();
+        order.updated_at = timestamp;
         
         // Handle market orders as aggressive IOC orders
         let effective_tif = if order.order_type == OrderType::Market {
@@ -150,24 +284,39 @@ impl MatchingEngine {
             return Err(MatchingError::InvalidOrder("Limit order must have a price".into()));
         }
         
+        // A GTX order that would take liquidity is repriced or cancelled, never matched
+        let crossing_action = match effective_tif {
+            TimeInForce::GTX => self.apply_crossing_policy(&mut order),
+            _ => None,
+        };
+        if crossing_action == Some(CrossingAction::Rejected) {
+            order.status = order.status.cancel()?;
+            return Ok(MatchResult {
+                sequence_id: order.sequence_id,
+                timestamp,
+                crossing_action,
+                processed_order: Some(order),
+                ..MatchResult::default()
+            });
+        }
+        
         // Match the order against the book
-        let mut result = self.match_order(&mut order)?;
+        let mut result = self.match_order(&mut order, timestamp)?;
         
         // If it's an IOC order and not fully filled, cancel the remainder
         if effective_tif == TimeInForce::IOC && order.status != OrderStatus::Filled {
             // For IOC, we don't add to the book, just mark it cancelled
-            if order.status == OrderStatus::New {
-                order.status = OrderStatus::Cancelled;
-            } else {
-                order.status = OrderStatus::PartiallyFilledCancelled;
-            }
+            order.status = order.status.cancel()?;
         } 
-        // If GTC and not fully filled, add to the book
+        // If GTC/GTX and not fully filled, add to the book
         else if order.status != OrderStatus::Filled {
             // Add remaining order to the book
-            self.add_to_book(&order);
+            self.add_to_book(&order, effective_tif);
         }
         
+        result.sequence_id = order.sequence_id;
+        result.timestamp = timestamp;
+        result.crossing_action = crossing_action;
         result.processed_order = Some(order);
         Ok(result)
     }
@@ -176,10 +325,11 @@ impl MatchingEngine {
     ///
     /// # Arguments
     /// * `order` - The order to match
+    /// * `timestamp` - Time to stamp the generated trades with
     ///
     /// # Returns
     /// A `MatchResult` containing the trades generated
-    fn match_order(&mut self, order: &mut Order) -> MatchingResult<MatchResult> {
+    fn match_order(&mut self, order: &mut Order, timestamp: DateTime<Utc>) -> MatchingResult<MatchResult> {
         let mut result = MatchResult::default();
         
         // Find the opposite side
@@ -192,20 +342,21 @@ impl MatchingEngine {
         loop {
             // Exit if order is fully filled
             if order.remaining_base.is_zero() {
-                order.status = OrderStatus::Filled;
+                order.status = order.status.transition(OrderStatus::Filled)?;
                 break;
             }
             
-            // Get the best opposing order
-            let best_opposing_order = match opposite_side {
-                Side::Bid => self.order_book.get_best_bid(),
-                Side::Ask => self.order_book.get_best_ask(),
+            // Get the best opposing price
+            let best_price = match opposite_side {
+                Side::Bid => self.order_book.best_bid(),
+                Side::Ask => self.order_book.best_ask(),
             };
             
             // Check if there's no matching order
-            if best_opposing_order.is_none() {
-                break;
-            }
+            let best_price = match best_price {
+                Some(price) => price,
+                None => break,
+            };
             
             // For limit orders, check if the price is acceptable
             if order.order_type == OrderType::Limit {
@@ -214,16 +365,6 @@ impl MatchingEngine {
                     None => return Err(MatchingError::InvalidOrder("Limit order must have a price".to_string())),
                 };
                 
-                let best_opposing_order_ref = match best_opposing_order {
-                    Some(order) => order,
-                    None => break,
-                };
-                
-                let best_price = match best_opposing_order_ref.limit_price {
-                    Some(price) => price,
-                    None => return Err(MatchingError::InvalidOrder("Opposing order must have a price".to_string())),
-                };
-                
                 // Check if price is acceptable based on order side
                 let price_acceptable = match order.side {
                     Side::Bid => best_price <= limit_price, // Buy: best ask <= my bid
@@ -235,74 +376,44 @@ impl MatchingEngine {
                 }
             }
             
-            // Get the best opposing order and remove it from the book
-            let best_opposing_order_ref = match best_opposing_order {
-                Some(order) => order,
+            // Fill the resting order at the head of the best level in place
+            let fill = match self.order_book.fill_best_order(opposite_side, order.remaining_base, timestamp) {
+                Some(fill) => fill,
                 None => break,
             };
             
-            let best_price = match best_opposing_order_ref.limit_price {
-                Some(price) => price,
-                None => return Err(MatchingError::InvalidOrder("Opposing order must have a price".to_string())),
-            };
-            
-            let opposing_order_id = best_opposing_order_ref.id;
-            
-            let mut opposing_order = match self.order_book.remove_order(
-                opposing_order_id,
-                opposite_side,
-                best_price
-            ) {
-                Some(order) => order,
-                None => return Err(MatchingError::OrderNotFound(opposing_order_id)),
-            };
-            
-            // Remove from our index
-            self.order_index.remove(&opposing_order.id);
-            
-            // Calculate matched quantity
-            let matched_qty = Decimal::min(order.remaining_base, opposing_order.remaining_base);
-            
-            // Calculate quote amount
-            let quote_amount = matched_qty * best_price;
+            // Fully filled makers have left the book
+            if fill.maker_status == OrderStatus::Filled {
+                self.order_index.remove(&fill.maker_order_id);
+            }
             
             // Create trade record
             let trade = Trade {
                 id: Uuid::new_v4(),
                 instrument_id: self.instrument_id,
-                maker_order_id: opposing_order.id,
+                maker_order_id: fill.maker_order_id,
                 taker_order_id: order.id,
-                base_amount: matched_qty,
-                quote_amount,
-                price: best_price,
-                created_at: Utc::now(),
+                maker_account_id: fill.maker_account_id,
+                taker_account_id: order.account_id,
+                taker_side: order.side,
+                base_amount: fill.base_amount,
+                quote_amount: fill.quote_amount,
+                price: fill.price,
+                created_at: timestamp,
             };
             
-            // Update order states
-            order.remaining_base -= matched_qty;
-            order.filled_base += matched_qty;
-            order.filled_quote += quote_amount;
-            
-            opposing_order.remaining_base -= matched_qty;
-            opposing_order.filled_base += matched_qty;
-            opposing_order.filled_quote += quote_amount;
+            // Update taker state
+            order.remaining_base -= fill.base_amount;
+            order.filled_base += fill.base_amount;
+            order.filled_quote += fill.quote_amount;
             
-            // Update order statuses
             if order.status == OrderStatus::New && !order.remaining_base.is_zero() {
-                order.status = OrderStatus::PartiallyFilled;
-            }
-            
-            if opposing_order.remaining_base.is_zero() {
-                opposing_order.status = OrderStatus::Filled;
-            } else {
-                opposing_order.status = OrderStatus::PartiallyFilled;
-                // Put partially filled order back in the book
-                self.add_to_book(&opposing_order);
+                order.status = order.status.transition(OrderStatus::PartiallyFilled)?;
             }
             
-            // Record trade and affected order
+            // Record trade and maker-side outcome
             result.trades.push(trade);
-            result.affected_orders.push(opposing_order);
+            result.fills.push(fill);
         }
         
         // For market orders with no matches, return an error
@@ -315,11 +426,11 @@ impl MatchingEngine {
         Ok(result)
     }
     
-    /// Adds an order to the book and updates the index.
-    fn add_to_book(&mut self, order: &Order) {
+    /// Adds an order resting under `time_in_force` to the book and updates the index.
+    fn add_to_book(&mut self, order: &Order, time_in_force: TimeInForce) {
         if let Some(price) = order.limit_price {
             self.order_book.add_order(order.clone());
-            self.order_index.insert(order.id, (order.side, price));
+            self.order_index.insert(order.id, (order.side, price, time_in_force));
         }
     }
     
@@ -329,24 +440,140 @@ impl MatchingEngine {
     /// * `order_id` - The ID of the order to cancel
     ///
     /// # Returns
-    /// The cancelled order if found
-    pub fn cancel_order(&mut self, order_id: Uuid) -> MatchingResult<Order> {
+    /// A `MatchResult` whose `processed_order` is the cancelled order, stamped with the cancel's
+    /// sequence ID and timestamp so it can be ordered against fills
+    pub fn cancel_order(&mut self, order_id: Uuid) -> MatchingResult<MatchResult> {
         // Look up the order location in our index
-        if let Some((side, price)) = self.order_index.remove(&order_id) {
-            if let Some(mut order) = self.order_book.remove_order(order_id, side, price) {
-                // Update order status
-                if order.status == OrderStatus::PartiallyFilled {
-                    order.status = OrderStatus::PartiallyFilledCancelled;
-                } else {
-                    order.status = OrderStatus::Cancelled;
-                }
-                return Ok(order);
-            }
+        if let Some((side, price, _)) = self.order_index.remove(&order_id)
+            && let Some(mut order) = self.order_book.remove_order(order_id, side, price)
+        {
+            let sequence_id = self.next_sequence();
+            let timestamp = self.clock.now();
+            // Update order status
+            order.status = order.status.cancel()?;
+            order.updated_at = timestamp;
+            return Ok(MatchResult {
+                processed_order: Some(order),
+                sequence_id,
+                timestamp,
+                ..MatchResult::default()
+            });
         }
         
         Err(MatchingError::OrderNotFound(order_id))
     }
     
+    /// Amends the price and/or quantity of a resting order.
+    ///
+    /// # Arguments
+    /// * `order_id` - The ID of the order to amend
+    /// * `new_price` - The new limit price, or `None` to keep the current one
+    /// * `new_base_amount` - The new total quantity, or `None` to keep the current one
+    ///
+    /// # Returns
+    /// A `MatchResult` whose `processed_order` is the amended order. A price change may
+    /// cross the book, in which case the resulting trades are included.
+    ///
+    /// # Notes
+    /// - Reducing quantity at the same price keeps the order's time priority
+    /// - Changing price or increasing quantity re-queues the order with a new sequence ID
+    /// - The new quantity must be greater than what has already been filled
+    /// - A GTX order whose new price would cross goes through the engine's `CrossingPolicy`,
+    ///   and is cancelled if the policy rejects it
+    pub fn amend_order(
+        &mut self,
+        order_id: Uuid,
+        new_price: Option<Decimal>,
+        new_base_amount: Option<Decimal>,
+    ) -> MatchingResult<MatchResult> {
+        let (side, price, time_in_force) = match self.order_index.get(&order_id) {
+            Some(&location) => location,
+            None => return Err(MatchingError::OrderNotFound(order_id)),
+        };
+
+        let (filled_base, base_amount, remaining_base) = match self
+            .order_book
+            .get_orders_at_price(side, price)
+            .and_then(|orders| orders.iter().find(|o| o.id == order_id))
+        {
+            Some(order) => (order.filled_base, order.base_amount, order.remaining_base),
+            None => return Err(MatchingError::OrderNotFound(order_id)),
+        };
+
+        let target_price = new_price.unwrap_or(price);
+        let target_base = new_base_amount.unwrap_or(base_amount);
+        if target_price <= Decimal::ZERO {
+            return Err(MatchingError::InvalidOrder("Amended price must be positive".into()));
+        }
+        if target_base <= filled_base {
+            return Err(MatchingError::InvalidOrder(
+                format!("Amended quantity {} must exceed filled quantity {}", target_base, filled_base)
+            ));
+        }
+        let target_remaining = target_base - filled_base;
+        let sequence_id = self.next_sequence();
+        let timestamp = self.clock.now();
+
+        // Shrinking at the same price is done in place so the order keeps its queue position
+        if target_price == price && target_remaining <= remaining_base {
+            let order = if target_remaining == remaining_base {
+                self.order_book
+                    .get_orders_at_price(side, price)
+                    .and_then(|orders| orders.iter().find(|o| o.id == order_id))
+            } else {
+                self.order_book.reduce_order(order_id, side, price, remaining_base - target_remaining, timestamp)
+            };
+            return match order {
+                Some(order) => Ok(MatchResult {
+                    processed_order: Some(order.clone()),
+                    sequence_id,
+                    timestamp,
+                    ..MatchResult::default()
+                }),
+                None => Err(MatchingError::OrderNotFound(order_id)),
+            };
+        }
+
+        // Anything else forfeits priority: pull the order and run it through matching again
+        self.order_index.remove(&order_id);
+        let mut order = match self.order_book.remove_order(order_id, side, price) {
+            Some(order) => order,
+            None => return Err(MatchingError::OrderNotFound(order_id)),
+        };
+        order.limit_price = Some(target_price);
+        order.base_amount = target_base;
+        order.remaining_base = target_remaining;
+        order.remaining_quote = target_remaining * target_price;
+        order.updated_at = timestamp;
+        order.sequence_id = sequence_id;
+
+        // A GTX order must not take liquidity through an amendment either
+        let crossing_action = match time_in_force {
+            TimeInForce::GTX => self.apply_crossing_policy(&mut order),
+            _ => None,
+        };
+        if crossing_action == Some(CrossingAction::Rejected) {
+            order.status = order.status.cancel()?;
+            return Ok(MatchResult {
+                sequence_id,
+                timestamp,
+                crossing_action,
+                processed_order: Some(order),
+                ..MatchResult::default()
+            });
+        }
+
+        let mut result = self.match_order(&mut order, timestamp)?;
+        if order.status != OrderStatus::Filled {
+            self.add_to_book(&order, time_in_force);
+        }
+        result.sequence_id = sequence_id;
+        result.timestamp = timestamp;
+        result.crossing_action = crossing_action;
+        result.processed_order = Some(order);
+        Ok(result)
+    }
+
     /// Gets the current state of the order book.
     pub fn order_book(&self) -> &OrderBook {
         &self.order_book
@@ -362,7 +589,6 @@ impl MatchingEngine {
 mod tests {
     use super::*;
     use rust_decimal_macros::dec;
-    use crate::types::CreatedFrom;
     
     // Helper function to create test orders
     fn create_test_order(
@@ -372,34 +598,17 @@ mod tests {
         quantity: Decimal,
         instrument_id: Uuid
     ) -> Order {
-        let now = Utc::now();
-        let remaining_quote = match price {
-            Some(p) => p * quantity,
-            None => dec!(0),
-        };
-        
-        Order {
-            id: Uuid::new_v4(),
-            ext_id: Some("test-order".to_string()),
-            account_id: Uuid::new_v4(),
-            order_type,
-            instrument_id,
-            side,
-            limit_price: price,
-            trigger_price: None,
-            base_amount: quantity,
-            remaining_base: quantity,
-            filled_quote: dec!(0.0),
-            filled_base: dec!(0.0),
-            remaining_quote,
-            expiration_date: now + chrono::Duration::days(365),
-            status: OrderStatus::New,
-            created_at: now,
-            updated_at: now,
-            trigger_by: None,
-            created_from: CreatedFrom::Api,
-            sequence_id: 0, // Will be set by engine
+        let mut builder = Order::builder()
+            .ext_id("test-order")
+            .account_id(Uuid::new_v4())
+            .instrument_id(instrument_id)
+            .side(side)
+            .order_type(order_type)
+            .base_amount(quantity);
+        if let Some(price) = price {
+            builder = builder.limit_price(price);
         }
+        builder.build().expect("test order is valid")
     }
     
     #[test]
@@ -610,8 +819,9 @@ mod tests {
         let order_id = processed_order.id;
         
         // Cancel the order
-        let cancelled = match engine.cancel_order(order_id) {
-            Ok(order) => order,
+        let cancelled = match engine.cancel_order(order_id).map(|result| result.processed_order) {
+            Ok(Some(order)) => order,
+            Ok(None) => panic!("Expected cancelled order to be present"),
             Err(e) => panic!("Failed to cancel order: {:?}", e),
         };
         assert_eq!(cancelled.status, OrderStatus::Cancelled);
@@ -619,4 +829,328 @@ mod tests {
         // Verify it's gone from the book
         assert!(engine.order_book.get_best_bid().is_none());
     }
+    
+    #[test]
+    fn test_amend_reduce_keeps_priority() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        
+        let first = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(2.0), instrument_id);
+        let second = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let first_id = first.id;
+        engine.process_order(first, TimeInForce::GTC).unwrap();
+        engine.process_order(second, TimeInForce::GTC).unwrap();
+        
+        let result = engine.amend_order(first_id, None, Some(dec!(0.5))).unwrap();
+        assert!(result.trades.is_empty());
+        let amended = result.processed_order.unwrap();
+        assert_eq!(amended.base_amount, dec!(0.5));
+        assert_eq!(amended.remaining_base, dec!(0.5));
+        
+        // Still first in the queue, with level volume reduced
+        assert_eq!(engine.order_book.get_best_bid().unwrap().id, first_id);
+        assert_eq!(engine.order_book.volume_at_price(Side::Bid, dec!(100.0)), Some(dec!(1.5)));
+    }
+    
+    #[test]
+    fn test_amend_increase_loses_priority() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        
+        let first = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let second = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let (first_id, second_id) = (first.id, second.id);
+        engine.process_order(first, TimeInForce::GTC).unwrap();
+        engine.process_order(second, TimeInForce::GTC).unwrap();
+        
+        let amended = engine.amend_order(first_id, None, Some(dec!(3.0))).unwrap().processed_order.unwrap();
+        assert_eq!(amended.remaining_base, dec!(3.0));
+        assert_eq!(engine.order_book.get_best_bid().unwrap().id, second_id);
+        assert_eq!(engine.order_book.volume_at_price(Side::Bid, dec!(100.0)), Some(dec!(4.0)));
+    }
+    
+    #[test]
+    fn test_amend_requeue_resets_queue_time() {
+        use crate::clock::ManualClock;
+
+        let instrument_id = Uuid::new_v4();
+        let clock = ManualClock::new(Utc::now() + chrono::Duration::seconds(10));
+        let mut engine = MatchingEngine::with_time_source(instrument_id, clock.clone());
+
+        let first = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let second = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let (first_id, second_id) = (first.id, second.id);
+        let first_queued_at = engine.process_order(first, TimeInForce::GTC).unwrap().timestamp;
+        clock.advance(chrono::Duration::seconds(1));
+        let second_queued_at = engine.process_order(second, TimeInForce::GTC).unwrap().timestamp;
+        assert_eq!(engine.order_book.depth(1).bids[0].oldest_order_at, first_queued_at);
+
+        // Growing the first order sends it to the back; the second has now waited longest
+        clock.advance(chrono::Duration::seconds(1));
+        let amended = engine.amend_order(first_id, None, Some(dec!(2.0))).unwrap();
+        assert_eq!(engine.order_book.depth(1).bids[0].oldest_order_at, second_queued_at);
+
+        engine.cancel_order(second_id).unwrap();
+        assert_eq!(engine.order_book.depth(1).bids[0].oldest_order_at, amended.timestamp);
+    }
+
+    #[test]
+    fn test_amend_price_crosses_book() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        
+        let ask = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(101.0)), dec!(1.0), instrument_id);
+        let bid = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(99.0)), dec!(1.0), instrument_id);
+        let bid_id = bid.id;
+        engine.process_order(ask, TimeInForce::GTC).unwrap();
+        engine.process_order(bid, TimeInForce::GTC).unwrap();
+        
+        let result = engine.amend_order(bid_id, Some(dec!(101.0)), None).unwrap();
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(result.trades[0].price, dec!(101.0));
+        assert_eq!(result.processed_order.unwrap().status, OrderStatus::Filled);
+        assert!(engine.order_book.get_best_bid().is_none());
+        assert!(engine.order_book.get_best_ask().is_none());
+    }
+    
+    #[test]
+    fn test_amend_rejects_invalid_requests() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        
+        let missing = Uuid::new_v4();
+        assert_eq!(engine.amend_order(missing, Some(dec!(1.0)), None).unwrap_err(), MatchingError::OrderNotFound(missing));
+        
+        // Partially fill a resting bid, then try to shrink it below the filled amount
+        let bid = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(2.0), instrument_id);
+        let bid_id = bid.id;
+        engine.process_order(bid, TimeInForce::GTC).unwrap();
+        let ask = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(100.0)), dec!(1.5), instrument_id);
+        engine.process_order(ask, TimeInForce::GTC).unwrap();
+        
+        let result = engine.amend_order(bid_id, None, Some(dec!(1.5)));
+        assert!(matches!(result, Err(MatchingError::InvalidOrder(_))));
+        assert_eq!(engine.order_book.volume_at_price(Side::Bid, dec!(100.0)), Some(dec!(0.5)));
+    }
+    
+    #[test]
+    fn test_partial_fill_keeps_maker_priority() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        
+        let first = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(100.0)), dec!(2.0), instrument_id);
+        let second = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let first_id = first.id;
+        engine.process_order(first, TimeInForce::GTC).unwrap();
+        engine.process_order(second, TimeInForce::GTC).unwrap();
+        
+        let taker = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(0.5), instrument_id);
+        let result = engine.process_order(taker, TimeInForce::GTC).unwrap();
+        assert_eq!(result.fills.len(), 1);
+        assert_eq!(result.fills[0].maker_order_id, first_id);
+        assert_eq!(result.fills[0].maker_remaining_base, dec!(1.5));
+        assert_eq!(result.fills[0].maker_status, OrderStatus::PartiallyFilled);
+        
+        // The partially filled maker is still first in line and still cancellable
+        assert_eq!(engine.order_book.get_best_ask().unwrap().id, first_id);
+        let cancelled = engine.cancel_order(first_id).unwrap().processed_order.unwrap();
+        assert_eq!(cancelled.status, OrderStatus::PartiallyFilledCancelled);
+    }
+    
+    #[test]
+    fn test_sweep_reports_fill_per_level() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        
+        for price in [dec!(100.0), dec!(101.0), dec!(102.0)] {
+            let ask = create_test_order(Side::Ask, OrderType::Limit, Some(price), dec!(1.0), instrument_id);
+            engine.process_order(ask, TimeInForce::GTC).unwrap();
+        }
+        
+        let taker = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(101.0)), dec!(3.0), instrument_id);
+        let result = engine.process_order(taker, TimeInForce::GTC).unwrap();
+        assert_eq!(result.trades.len(), 2);
+        assert_eq!(result.fills.iter().map(|f| f.price).collect::<Vec<_>>(), vec![dec!(100.0), dec!(101.0)]);
+        assert!(result.fills.iter().all(|f| f.maker_status == OrderStatus::Filled));
+        
+        // Remainder rests at the taker's limit, below the untouched 102 ask
+        let rested = result.processed_order.unwrap();
+        assert_eq!(rested.status, OrderStatus::PartiallyFilled);
+        assert_eq!(engine.order_book.best_bid(), Some(dec!(101.0)));
+        assert_eq!(engine.order_book.best_ask(), Some(dec!(102.0)));
+    }
+    
+    #[test]
+    fn test_rejects_unmatchable_orders_without_touching_book() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        for price in [dec!(100.0), dec!(101.0)] {
+            let ask = create_test_order(Side::Ask, OrderType::Limit, Some(price), dec!(1.0), instrument_id);
+            engine.process_order(ask, TimeInForce::GTC).unwrap();
+        }
+        let before = engine.order_book.depth(usize::MAX);
+
+        // A Stop order still waiting for its trigger
+        let stop = Order::builder()
+            .account_id(Uuid::new_v4())
+            .instrument_id(instrument_id)
+            .side(Side::Bid)
+            .order_type(OrderType::Stop)
+            .trigger_price(dec!(100.0))
+            .base_amount(dec!(2.0))
+            .build()
+            .unwrap();
+        assert_eq!(stop.status, OrderStatus::WaitingTrigger);
+        assert!(matches!(engine.process_order(stop, TimeInForce::GTC), Err(MatchingError::InvalidOrder(_))));
+
+        // A limit order that was already filled elsewhere
+        let mut filled = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(101.0)), dec!(2.0), instrument_id);
+        filled.status = OrderStatus::Filled;
+        assert!(matches!(engine.process_order(filled, TimeInForce::GTC), Err(MatchingError::InvalidOrder(_))));
+
+        assert_eq!(engine.order_book.depth(usize::MAX), before);
+        assert_eq!(engine.order_index.len(), 2);
+    }
+
+    #[test]
+    fn test_results_stamped_from_time_source() {
+        use crate::clock::ManualClock;
+        
+        let instrument_id = Uuid::new_v4();
+        // Well away from wall-clock time so builder timestamps cannot coincide with engine ones
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let clock = ManualClock::new(start);
+        let mut engine = MatchingEngine::with_time_source(instrument_id, clock.clone());
+        
+        let ask = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(100.0)), dec!(2.0), instrument_id);
+        let ask_id = ask.id;
+        let rested = engine.process_order(ask, TimeInForce::GTC).unwrap();
+        assert_eq!(rested.timestamp, start);
+        assert_eq!(rested.processed_order.as_ref().unwrap().updated_at, start);
+        assert_eq!(engine.order_book.get_best_ask().unwrap().updated_at, start);
+        
+        clock.advance(chrono::Duration::milliseconds(5));
+        let amended = engine.amend_order(ask_id, None, Some(dec!(1.5))).unwrap();
+        assert_eq!(amended.timestamp, start + chrono::Duration::milliseconds(5));
+        assert_eq!(amended.processed_order.unwrap().updated_at, amended.timestamp);
+        
+        clock.advance(chrono::Duration::milliseconds(5));
+        let taker = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let matched = engine.process_order(taker, TimeInForce::IOC).unwrap();
+        assert_eq!(matched.trades[0].created_at, start + chrono::Duration::milliseconds(10));
+        assert_eq!(matched.processed_order.as_ref().unwrap().updated_at, matched.timestamp);
+        assert_eq!(engine.order_book.get_best_ask().unwrap().updated_at, matched.timestamp);
+
+        clock.advance(chrono::Duration::milliseconds(5));
+        let cancelled = engine.cancel_order(ask_id).unwrap();
+        assert_eq!(cancelled.timestamp, start + chrono::Duration::milliseconds(15));
+        assert_eq!(cancelled.processed_order.unwrap().updated_at, cancelled.timestamp);
+
+        // Every command takes a fresh sequence number, including in-place amends and cancels
+        assert!(rested.sequence_id < amended.sequence_id);
+        assert!(amended.sequence_id < matched.sequence_id);
+        assert!(matched.sequence_id < cancelled.sequence_id);
+    }
+    
+    #[test]
+    fn test_gtx_rests_or_cancels_without_trading() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        let ask = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        engine.process_order(ask, TimeInForce::GTC).unwrap();
+        
+        // Below the ask it rests like GTC
+        let passive = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(99.0)), dec!(1.0), instrument_id);
+        let result = engine.process_order(passive, TimeInForce::GTX).unwrap();
+        assert!(result.trades.is_empty());
+        assert_eq!(result.processed_order.unwrap().status, OrderStatus::New);
+        assert_eq!(engine.order_book.best_bid(), Some(dec!(99.0)));
+        
+        // At the ask it would take liquidity, so it is cancelled and the ask is untouched
+        let crossing = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let crossing_id = crossing.id;
+        let result = engine.process_order(crossing, TimeInForce::GTX).unwrap();
+        assert!(result.trades.is_empty());
+        assert_eq!(result.processed_order.unwrap().status, OrderStatus::Cancelled);
+        assert_eq!(result.crossing_action, Some(CrossingAction::Rejected));
+        assert_eq!(engine.order_book.volume_at_price(Side::Ask, dec!(100.0)), Some(dec!(1.0)));
+        assert!(matches!(engine.cancel_order(crossing_id), Err(MatchingError::OrderNotFound(_))));
+        let crossing_sequence_id = result.sequence_id;
+        
+        // A GTX market order is refused before it is sequenced
+        let market = create_test_order(Side::Bid, OrderType::Market, None, dec!(1.0), instrument_id);
+        assert!(matches!(engine.process_order(market, TimeInForce::GTX), Err(MatchingError::InvalidOrder(_))));
+        let next = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(98.0)), dec!(1.0), instrument_id);
+        assert_eq!(engine.process_order(next, TimeInForce::GTC).unwrap().sequence_id, crossing_sequence_id + 1);
+    }
+    
+    #[test]
+    fn test_gtx_amend_never_takes_liquidity() {
+        let instrument_id = Uuid::new_v4();
+        let ask = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+        let bid = create_test_order(Side::Bid, OrderType::Limit, Some(dec!(99.0)), dec!(1.0), instrument_id);
+        let bid_id = bid.id;
+        let setup = |policy| {
+            let mut engine = MatchingEngine::new(instrument_id).with_crossing_policy(policy);
+            engine.process_order(ask.clone(), TimeInForce::GTC).unwrap();
+            engine.process_order(bid.clone(), TimeInForce::GTX).unwrap();
+            engine
+        };
+
+        // Rejected: the amendment cancels the order instead of trading
+        let mut engine = setup(CrossingPolicy::Reject);
+        let result = engine.amend_order(bid_id, Some(dec!(100.0)), None).unwrap();
+        assert!(result.trades.is_empty());
+        assert_eq!(result.crossing_action, Some(CrossingAction::Rejected));
+        assert_eq!(result.processed_order.unwrap().status, OrderStatus::Cancelled);
+        assert_eq!(engine.order_book.best_bid(), None);
+        assert_eq!(engine.order_book.volume_at_price(Side::Ask, dec!(100.0)), Some(dec!(1.0)));
+        assert!(matches!(engine.cancel_order(bid_id), Err(MatchingError::OrderNotFound(_))));
+
+        // Repriced: the order stays GTX at its new passive price, so a second crossing amend is caught too
+        let mut engine = setup(CrossingPolicy::RepriceInside { tick_size: dec!(0.5) });
+        let result = engine.amend_order(bid_id, Some(dec!(100.0)), None).unwrap();
+        assert!(result.trades.is_empty());
+        assert_eq!(result.crossing_action, Some(CrossingAction::Repriced { from: dec!(100.0), to: dec!(99.5) }));
+        assert_eq!(engine.order_book.best_bid(), Some(dec!(99.5)));
+        let result = engine.amend_order(bid_id, Some(dec!(101.0)), Some(dec!(2.0))).unwrap();
+        assert!(result.trades.is_empty());
+        assert_eq!(engine.order_book.volume_at_price(Side::Bid, dec!(99.5)), Some(dec!(2.0)));
+    }
+
+    #[test]
+    fn test_crossing_policy_reprices_gtx() {
+        let instrument_id = Uuid::new_v4();
+        let crossing_bid = |price| create_test_order(Side::Bid, OrderType::Limit, Some(price), dec!(1.0), instrument_id);
+        let setup = |policy| {
+            let mut engine = MatchingEngine::new(instrument_id).with_crossing_policy(policy);
+            let ask = create_test_order(Side::Ask, OrderType::Limit, Some(dec!(100.0)), dec!(1.0), instrument_id);
+            engine.process_order(ask, TimeInForce::GTC).unwrap();
+            engine
+        };
+        
+        // One tick inside the opposite touch
+        let mut engine = setup(CrossingPolicy::RepriceInside { tick_size: dec!(0.5) });
+        let result = engine.process_order(crossing_bid(dec!(101.0)), TimeInForce::GTX).unwrap();
+        assert!(result.trades.is_empty());
+        assert_eq!(result.crossing_action, Some(CrossingAction::Repriced { from: dec!(101.0), to: dec!(99.5) }));
+        let rested = result.processed_order.unwrap();
+        assert_eq!((rested.limit_price, rested.remaining_quote), (Some(dec!(99.5)), dec!(99.5)));
+        assert_eq!(engine.order_book.best_bid(), Some(dec!(99.5)));
+        
+        // Joins the own-side touch, or is rejected when there is none
+        let mut engine = setup(CrossingPolicy::QueueAtTouch);
+        let result = engine.process_order(crossing_bid(dec!(100.0)), TimeInForce::GTX).unwrap();
+        assert_eq!(result.crossing_action, Some(CrossingAction::Rejected));
+        engine.process_order(crossing_bid(dec!(98.0)), TimeInForce::GTC).unwrap();
+        let result = engine.process_order(crossing_bid(dec!(100.0)), TimeInForce::GTX).unwrap();
+        assert_eq!(result.crossing_action, Some(CrossingAction::Repriced { from: dec!(100.0), to: dec!(98.0) }));
+        assert_eq!(engine.order_book.order_count_at_price(Side::Bid, dec!(98.0)), 2);
+        
+        // A tick size that would still cross falls back to rejecting
+        let mut engine = setup(CrossingPolicy::RepriceInside { tick_size: dec!(0) });
+        let result = engine.process_order(crossing_bid(dec!(100.0)), TimeInForce::GTX).unwrap();
+        assert_eq!(result.crossing_action, Some(CrossingAction::Rejected));
+        assert_eq!(engine.crossing_policy(), CrossingPolicy::RepriceInside { tick_size: dec!(0) });
+    }
 }
@@ -19,10 +19,16 @@
 // |               |                                                    | is_empty                |
 // |               |                                                    | order_count             |
 // |--------------|---------------------------------------------------|-------------------------|
+// | DepthLevel    | Price, volume, order count and longest queue wait |                         |
+// | DepthSnapshot | Top-of-book levels for both sides                 |                         |
+// |--------------|---------------------------------------------------|-------------------------|
 // | OrderBook     | Main order book implementation                    | add_order               |
 // |               |                                                    | remove_order            |
+// |               |                                                    | reduce_order            |
+// |               |                                                    | fill_best_order         |
 // |               |                                                    | peek_best_order         |
 // |               |                                                    | get_orders_at_price     |
+// |               |                                                    | depth                   |
 //
 //--------------------------------------------------------------------------------------------------
 // FUNCTIONS
@@ -32,11 +38,15 @@
 // | new                   | Creates new OrderBook                     | OrderBook             |
 // | add_order            | Adds order to book                        | ()                    |
 // | remove_order         | Removes order from book                   | Option<Order>         |
+// | reduce_order         | Shrinks a resting order in place          | Option<&Order>        |
+// | fill_best_order      | Fills the head of the best level in place | Option<Fill>          |
 // | peek_best_order      | Gets next order without removing         | Option<&Order>        |
 // | best_bid             | Gets best bid price                      | Option<Decimal>       |
 // | best_ask             | Gets best ask price                      | Option<Decimal>       |
 // | spread               | Gets current spread                      | Option<Decimal>       |
+// | would_cross          | Checks if a price would trade on entry   | bool                  |
 // | volume_at_price      | Gets volume at price level              | Option<Decimal>       |
+// | depth                | Gets top N aggregated levels per side    | DepthSnapshot         |
 //
 //--------------------------------------------------------------------------------------------------
 // TESTS
@@ -51,14 +61,19 @@
 // | test_spread_calculation      | Tests spread calculations                               |
 // | test_fifo_order_execution    | Tests FIFO ordering of orders                          |
 // | test_order_count_tracking    | Tests order counting at price levels                    |
+// | test_reduce_order_keeps_priority | Tests in-place reduction keeps FIFO position         |
+// | test_depth_snapshot          | Tests level ordering and truncation in depth snapshots  |
+// | test_fill_best_order         | Tests in-place partial and full fills at the head       |
+// | test_queue_time_resets_on_requeue | Tests re-queued orders report their new entry time |
 //--------------------------------------------------------------------------------------------------
 
 use std::collections::{BTreeMap, VecDeque};
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
 // Import types from types.rs
-use crate::types::{Order, Side, OrderStatus};
+use crate::types::{Order, Side, OrderStatus, Fill};
 
 /// Represents a price level in the order book, maintaining a FIFO queue of orders
 /// at the same price point.
@@ -70,6 +85,8 @@ pub struct PriceLevel {
     pub orders: VecDeque<Order>,
     /// Total volume of all orders at this price level
     pub total_volume: Decimal,
+    /// Time each order joined the queue, parallel to `orders`
+    queued_at: VecDeque<DateTime<Utc>>,
 }
 
 impl PriceLevel {
@@ -101,6 +118,31 @@ impl PriceLevel {
     }
 }
 
+/// Aggregated view of a single price level, as exposed in depth snapshots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthLevel {
+    /// The price for this level
+    pub price: Decimal,
+    /// Total remaining volume resting at this price
+    pub volume: Decimal,
+    /// Number of orders queued at this price
+    pub order_count: usize,
+    /// Time the order at the front of the queue joined it, i.e. the longest wait at this price.
+    /// An order re-queued by an amendment counts from its re-queue, not its creation.
+    pub oldest_order_at: DateTime<Utc>,
+}
+
+/// Aggregated top-of-book view of both sides, best prices first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthSnapshot {
+    /// Identifier for the instrument the snapshot was taken from
+    pub instrument_id: Uuid,
+    /// Bid levels ordered from highest to lowest price
+    pub bids: Vec<DepthLevel>,
+    /// Ask levels ordered from lowest to highest price
+    pub asks: Vec<DepthLevel>,
+}
+
 /// The main order book structure that maintains bid and ask orders in price-time priority.
 /// Uses BTreeMap for price level organization and VecDeque for FIFO ordering within price levels.
 #[derive(Debug)]
@@ -145,6 +187,7 @@ impl OrderBook {
     /// - Orders for different instruments are ignored
     /// - Market orders (no limit price) are ignored
     /// - Orders are added to the back of the queue at their price level
+    /// - The order's `updated_at` is recorded as the time it joined the queue
     /// - Best prices are automatically updated
     pub fn add_order(&mut self, order: Order) {
         // Verify this order is for our instrument
@@ -170,9 +213,11 @@ impl OrderBook {
                 price,
                 orders: VecDeque::new(),
                 total_volume: Decimal::ZERO,
+                queued_at: VecDeque::new(),
             });
 
         // Add the order to the back of the queue (FIFO)
+        price_level.queued_at.push_back(order.updated_at);
         price_level.orders.push_back(order.clone());
         price_level.total_volume += order.remaining_base;
 
@@ -207,6 +252,7 @@ impl OrderBook {
             if let Some(pos) = price_level.orders.iter().position(|o| o.id == order_id) {
                 // Use a safer approach to remove the order
                 if let Some(order) = price_level.orders.remove(pos) {
+                    price_level.queued_at.remove(pos);
                     price_level.total_volume -= order.remaining_base;
 
                     // If the price level is empty, remove it
@@ -223,6 +269,104 @@ impl OrderBook {
         None
     }
 
+    /// Fills the order at the head of the best price level on `side`, in place.
+    ///
+    /// # Arguments
+    /// * `side` - The side (Bid/Ask) holding the resting order to fill
+    /// * `max_quantity` - The most base quantity the incoming order can take
+    /// * `now` - Time to stamp the maker's `updated_at` with
+    ///
+    /// # Returns
+    /// * `Some(Fill)` - The maker-side outcome of the match
+    /// * `None` - If there are no orders on the specified side
+    ///
+    /// # Notes
+    /// - A partially filled maker stays at the head of its level, keeping time priority
+    /// - A fully filled maker is popped, and its level removed once empty
+    /// - Price acceptability is the caller's responsibility
+    pub fn fill_best_order(&mut self, side: Side, max_quantity: Decimal, now: DateTime<Utc>) -> Option<Fill> {
+        let (price_levels, best_price) = match side {
+            Side::Bid => (&mut self.bids, self.best_bid),
+            Side::Ask => (&mut self.asks, self.best_ask),
+        };
+
+        let price = best_price?;
+        let price_level = price_levels.get_mut(&price)?;
+        let order = price_level.orders.front_mut()?;
+
+        let quantity = Decimal::min(max_quantity, order.remaining_base);
+        let quote_amount = quantity * price;
+        order.remaining_base -= quantity;
+        order.filled_base += quantity;
+        order.filled_quote += quote_amount;
+        order.updated_at = now;
+        let next_status = if order.remaining_base.is_zero() {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        };
+        // Only New and PartiallyFilled orders rest on the book, and both may take a fill
+        debug_assert!(order.status.can_transition_to(next_status));
+        order.status = next_status;
+
+        let fill = Fill {
+            maker_order_id: order.id,
+            maker_account_id: order.account_id,
+            price,
+            base_amount: quantity,
+            quote_amount,
+            maker_remaining_base: order.remaining_base,
+            maker_status: order.status,
+        };
+        price_level.total_volume -= quantity;
+
+        if fill.maker_status == OrderStatus::Filled {
+            price_level.orders.pop_front();
+            price_level.queued_at.pop_front();
+            if price_level.orders.is_empty() {
+                price_levels.remove(&price);
+                self.update_best_prices();
+            }
+        }
+        Some(fill)
+    }
+
+    /// Reduces the size of a resting order without changing its queue position.
+    ///
+    /// # Arguments
+    /// * `order_id` - The unique identifier of the order to reduce
+    /// * `side` - The side (Bid/Ask) of the order
+    /// * `price` - The price level of the order
+    /// * `quantity` - The amount to take off both the total and remaining base quantity
+    /// * `now` - Time to stamp the order's `updated_at` with
+    ///
+    /// # Returns
+    /// * `Some(&Order)` - The updated order
+    /// * `None` - If the order was not found or `quantity` is not less than its remaining amount
+    ///
+    /// # Notes
+    /// - Increasing size must go through remove + add, since it forfeits time priority
+    /// - Updates total volume at the price level and the order's `updated_at`
+    pub fn reduce_order(&mut self, order_id: Uuid, side: Side, price: Decimal, quantity: Decimal, now: DateTime<Utc>) -> Option<&Order> {
+        let price_levels = match side {
+            Side::Bid => &mut self.bids,
+            Side::Ask => &mut self.asks,
+        };
+
+        let price_level = price_levels.get_mut(&price)?;
+        let order = price_level.orders.iter_mut().find(|o| o.id == order_id)?;
+        if quantity < Decimal::ZERO || quantity >= order.remaining_base {
+            return None;
+        }
+
+        order.base_amount -= quantity;
+        order.remaining_base -= quantity;
+        order.remaining_quote = order.remaining_base * price;
+        order.updated_at = now;
+        price_level.total_volume -= quantity;
+        Some(order)
+    }
+
     /// Returns the best order at a given side without removing it.
     ///
     /// # Arguments
@@ -324,6 +468,18 @@ impl OrderBook {
         }
     }
 
+    /// Returns true if an order on `side` at `price` would trade against the opposite side.
+    ///
+    /// # Arguments
+    /// * `side` - The side (Bid/Ask) of the incoming order
+    /// * `price` - The incoming order's limit price
+    pub fn would_cross(&self, side: Side, price: Decimal) -> bool {
+        match side {
+            Side::Bid => self.best_ask.is_some_and(|ask| price >= ask),
+            Side::Ask => self.best_bid.is_some_and(|bid| price <= bid),
+        }
+    }
+
     /// Returns the total volume at a specific price level.
     ///
     /// # Arguments
@@ -341,6 +497,28 @@ impl OrderBook {
         price_levels.get(&price).map(|level| level.total_volume)
     }
 
+    /// Returns an aggregated snapshot of the top `max_levels` price levels on each side.
+    ///
+    /// # Arguments
+    /// * `max_levels` - The maximum number of levels to include per side
+    ///
+    /// # Returns
+    /// * `DepthSnapshot` - Bids from highest price down, asks from lowest price up
+    pub fn depth(&self, max_levels: usize) -> DepthSnapshot {
+        // Empty levels are removed eagerly, so every level has a front order
+        let to_depth = |level: &PriceLevel| DepthLevel {
+            price: level.price,
+            volume: level.total_volume,
+            order_count: level.order_count(),
+            oldest_order_at: level.queued_at.front().copied().unwrap_or(DateTime::<Utc>::MIN_UTC),
+        };
+        DepthSnapshot {
+            instrument_id: self.instrument_id,
+            bids: self.bids.values().rev().take(max_levels).map(to_depth).collect(),
+            asks: self.asks.values().take(max_levels).map(to_depth).collect(),
+        }
+    }
+
     /// Returns the instrument ID this order book manages.
     ///
     /// # Returns
@@ -399,8 +577,7 @@ mod tests {
 
     use super::*;
     use rust_decimal_macros::dec;
-    use crate::types::{OrderType, CreatedFrom};
-    use chrono::Utc;
+    use crate::types::OrderType;
 
     /// Creates a test order with the specified parameters.
     ///
@@ -413,29 +590,16 @@ mod tests {
     /// # Returns
     /// A new Order instance with default values for other fields
     fn create_test_order(side: Side, price: Decimal, quantity: Decimal, instrument_id: Uuid) -> Order {
-        let now = Utc::now();
-        Order {
-            id: Uuid::new_v4(),
-            ext_id: Some("test-order".to_string()),
-            account_id: Uuid::new_v4(),
-            order_type: OrderType::Limit,
-            instrument_id,
-            side,
-            limit_price: Some(price),
-            trigger_price: None,
-            base_amount: quantity,
-            remaining_base: quantity,
-            filled_quote: dec!(0.0),
-            filled_base: dec!(0.0),
-            remaining_quote: price * quantity,
-            expiration_date: now + chrono::Duration::days(365),
-            status: OrderStatus::New,
-            created_at: now,
-            updated_at: now,
-            trigger_by: None,
-            created_from: CreatedFrom::Api,
-            sequence_id: 1,
-        }
+        Order::builder()
+            .ext_id("test-order")
+            .account_id(Uuid::new_v4())
+            .instrument_id(instrument_id)
+            .side(side)
+            .order_type(OrderType::Limit)
+            .limit_price(price)
+            .base_amount(quantity)
+            .build()
+            .expect("test order is valid")
     }
 
     /// Tests that a new orderbook is properly initialized empty.
@@ -546,6 +710,10 @@ mod tests {
         book.add_order(ask_order);
         
         assert_eq!(book.spread(), Some(dec!(1.0)));
+        assert!(book.would_cross(Side::Bid, dec!(101.0)));
+        assert!(!book.would_cross(Side::Bid, dec!(100.5)));
+        assert!(book.would_cross(Side::Ask, dec!(100.0)));
+        assert!(!book.would_cross(Side::Ask, dec!(100.5)));
     }
 
     /// Tests handling of orders for wrong instrument IDs.
@@ -637,14 +805,128 @@ mod tests {
         assert_eq!(book.order_count_at_price(Side::Bid, dec!(101.0)), 1);
     }
 
+    /// Tests that reducing an order in place keeps its queue position and volume in sync.
+    #[test]
+    fn test_reduce_order_keeps_priority() {
+        let instrument_id = Uuid::new_v4();
+        let mut book = OrderBook::new(instrument_id);
+
+        let first = create_test_order(Side::Ask, dec!(100.0), dec!(2.0), instrument_id);
+        let second = create_test_order(Side::Ask, dec!(100.0), dec!(1.0), instrument_id);
+        book.add_order(first.clone());
+        book.add_order(second);
+
+        let reduced = book.reduce_order(first.id, Side::Ask, dec!(100.0), dec!(1.5), Utc::now());
+        assert_eq!(reduced.map(|o| (o.base_amount, o.remaining_base)), Some((dec!(0.5), dec!(0.5))));
+        assert_eq!(book.volume_at_price(Side::Ask, dec!(100.0)), Some(dec!(1.5)));
+        assert_eq!(book.get_best_ask().map(|o| o.id), Some(first.id));
+
+        // Removing the whole remaining amount is a cancel, not a reduction
+        assert!(book.reduce_order(first.id, Side::Ask, dec!(100.0), dec!(0.5), Utc::now()).is_none());
+        assert_eq!(book.volume_at_price(Side::Ask, dec!(100.0)), Some(dec!(1.5)));
+    }
+
+    /// Tests that depth snapshots list best prices first and honor the level limit.
+    #[test]
+    fn test_depth_snapshot() {
+        let instrument_id = Uuid::new_v4();
+        let mut book = OrderBook::new(instrument_id);
+
+        for (price, quantity) in [(dec!(99.0), dec!(1.0)), (dec!(100.0), dec!(2.0)), (dec!(98.0), dec!(3.0))] {
+            book.add_order(create_test_order(Side::Bid, price, quantity, instrument_id));
+        }
+        // Joins the 100 level a second after its head, which must still be reported as the oldest
+        let mut later = create_test_order(Side::Bid, dec!(100.0), dec!(0.5), instrument_id);
+        later.updated_at += chrono::Duration::seconds(1);
+        let later_queued_at = later.updated_at;
+        book.add_order(later);
+        for price in [dec!(102.0), dec!(101.0)] {
+            book.add_order(create_test_order(Side::Ask, price, dec!(1.0), instrument_id));
+        }
+        let oldest_at_100 = book.get_best_bid().unwrap().updated_at;
+
+        let depth = book.depth(2);
+        let summary = |levels: &[DepthLevel]| levels.iter().map(|l| (l.price, l.volume, l.order_count)).collect::<Vec<_>>();
+        assert_eq!(depth.instrument_id, instrument_id);
+        assert_eq!(summary(&depth.bids), vec![(dec!(100.0), dec!(2.5), 2), (dec!(99.0), dec!(1.0), 1)]);
+        assert_eq!(summary(&depth.asks), vec![(dec!(101.0), dec!(1.0), 1), (dec!(102.0), dec!(1.0), 1)]);
+        assert_eq!(depth.bids[0].oldest_order_at, oldest_at_100);
+        assert!(depth.bids[0].oldest_order_at < later_queued_at);
+        assert!(OrderBook::new(instrument_id).depth(5).bids.is_empty());
+    }
+
+    /// Tests that fills mutate the head order in place and pop it once fully filled.
+    #[test]
+    fn test_fill_best_order() {
+        let instrument_id = Uuid::new_v4();
+        let mut book = OrderBook::new(instrument_id);
+        assert!(book.fill_best_order(Side::Ask, dec!(1.0), Utc::now()).is_none());
+
+        let first = create_test_order(Side::Ask, dec!(100.0), dec!(2.0), instrument_id);
+        let second = create_test_order(Side::Ask, dec!(100.0), dec!(1.0), instrument_id);
+        book.add_order(first.clone());
+        book.add_order(second.clone());
+        book.add_order(create_test_order(Side::Ask, dec!(101.0), dec!(1.0), instrument_id));
+
+        // Partial fill keeps the maker at the head of the level
+        let fill = book.fill_best_order(Side::Ask, dec!(0.5), Utc::now()).unwrap();
+        assert_eq!(fill.maker_order_id, first.id);
+        assert_eq!(fill.quote_amount, dec!(50.0));
+        assert_eq!(fill.maker_remaining_base, dec!(1.5));
+        assert_eq!(fill.maker_status, OrderStatus::PartiallyFilled);
+        let head = book.get_best_ask().unwrap();
+        assert_eq!((head.id, head.filled_base), (first.id, dec!(0.5)));
+        assert_eq!(book.volume_at_price(Side::Ask, dec!(100.0)), Some(dec!(2.5)));
+
+        // Fill is capped by the maker's remaining amount
+        let fill = book.fill_best_order(Side::Ask, dec!(5.0), Utc::now()).unwrap();
+        assert_eq!((fill.base_amount, fill.maker_status), (dec!(1.5), OrderStatus::Filled));
+        assert_eq!(book.get_best_ask().map(|o| o.id), Some(second.id));
+
+        // Emptying a level moves the best price
+        book.fill_best_order(Side::Ask, dec!(1.0), Utc::now());
+        assert_eq!(book.best_ask(), Some(dec!(101.0)));
+    }
+
+    /// Tests that an order removed and re-added, as an amendment does, reports its re-queue time.
+    #[test]
+    fn test_queue_time_resets_on_requeue() {
+        let instrument_id = Uuid::new_v4();
+        let mut book = OrderBook::new(instrument_id);
+
+        let first = create_test_order(Side::Ask, dec!(100.0), dec!(1.0), instrument_id);
+        let mut second = create_test_order(Side::Ask, dec!(100.0), dec!(1.0), instrument_id);
+        second.created_at += chrono::Duration::seconds(1);
+        second.updated_at = second.created_at;
+        book.add_order(first.clone());
+        book.add_order(second.clone());
+        assert_eq!(book.depth(1).asks[0].oldest_order_at, first.created_at);
+
+        // Re-queue the first order behind the second, keeping its original creation time
+        let mut amended = book.remove_order(first.id, Side::Ask, dec!(100.0)).unwrap();
+        amended.updated_at = second.created_at + chrono::Duration::seconds(1);
+        book.add_order(amended.clone());
+        assert_eq!(book.get_best_ask().map(|o| o.id), Some(second.id));
+        assert_eq!(book.depth(1).asks[0].oldest_order_at, second.created_at);
+
+        // Fills and reductions do not move an order in the queue
+        book.fill_best_order(Side::Ask, dec!(0.5), Utc::now());
+        book.reduce_order(amended.id, Side::Ask, dec!(100.0), dec!(0.5), Utc::now());
+        assert_eq!(book.depth(1).asks[0].oldest_order_at, second.created_at);
+        book.fill_best_order(Side::Ask, dec!(0.5), Utc::now());
+        assert_eq!(book.depth(1).asks[0].oldest_order_at, amended.updated_at);
+    }
+
     /// Tests various edge cases in order handling.
     #[test]
     fn test_edge_cases() {
         let instrument_id = Uuid::new_v4();
         let mut book = OrderBook::new(instrument_id);
         
-        // Test zero quantity
-        let zero_order = create_test_order(Side::Bid, dec!(100.0), dec!(0.0), instrument_id);
+        // Test zero quantity; the builder rejects it, so zero out a valid order
+        let mut zero_order = create_test_order(Side::Bid, dec!(100.0), dec!(1.0), instrument_id);
+        zero_order.base_amount = dec!(0.0);
+        zero_order.remaining_base = dec!(0.0);
         book.add_order(zero_order);
         assert_eq!(book.volume_at_price(Side::Bid, dec!(100.0)), Some(dec!(0.0)));
         
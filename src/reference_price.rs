@@ -0,0 +1,230 @@
+//--------------------------------------------------------------------------------------------------
+// MODULE OVERVIEW
+//--------------------------------------------------------------------------------------------------
+// This module tracks the reference prices conditional orders are triggered against, and decides
+// whether a Stop/StopLimit order should activate according to its `trigger_by`.
+//
+// | Price   | Source                                                                          |
+// |---------|---------------------------------------------------------------------------------|
+// | Last    | Price of the most recent trade                                                  |
+// | Mark    | Book mid when both sides are quoted, otherwise the last trade                   |
+// | Index   | Pushed in by an external index feed adapter                                     |
+//
+//--------------------------------------------------------------------------------------------------
+// STRUCTS
+//--------------------------------------------------------------------------------------------------
+// | Name            | Description                                      | Key Methods              |
+// |-----------------|--------------------------------------------------|--------------------------|
+// | ReferencePrices | Last, mark and index price for one instrument    | on_trades                |
+// |                 |                                                  | update_from_book         |
+// |                 |                                                  | set_index_price          |
+// |                 |                                                  | price_for                |
+// |                 |                                                  | is_triggered             |
+//
+//--------------------------------------------------------------------------------------------------
+// TESTS
+//--------------------------------------------------------------------------------------------------
+// | Name                                | Description                                         |
+// |-------------------------------------|-----------------------------------------------------|
+// | test_mark_prefers_mid_over_last     | Mark uses the mid, falling back to the last trade   |
+// | test_trigger_direction_by_side      | Buy stops trigger at/above, sell stops at/below     |
+// | test_trigger_by_selects_price       | trigger_by picks last, mark or index price          |
+//--------------------------------------------------------------------------------------------------
+
+use rust_decimal::Decimal;
+
+use crate::orderbook::OrderBook;
+use crate::types::{Order, Side, Trade, TriggerType};
+
+/// Reference prices for a single instrument.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReferencePrices {
+    /// Price of the most recent trade
+    last_price: Option<Decimal>,
+    /// Book mid at the last update, if both sides were quoted
+    mid_price: Option<Decimal>,
+    /// Latest price from the external index feed
+    index_price: Option<Decimal>,
+}
+
+impl ReferencePrices {
+    /// Creates a tracker with no prices known yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the most recent of `trades` as the last traded price.
+    pub fn on_trades(&mut self, trades: &[Trade]) {
+        if let Some(trade) = trades.last() {
+            self.last_price = Some(trade.price);
+        }
+    }
+
+    /// Refreshes the mid price from the current top of `book`.
+    pub fn update_from_book(&mut self, book: &OrderBook) {
+        self.mid_price = match (book.best_bid(), book.best_ask()) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+            _ => None,
+        };
+    }
+
+    /// Sets the index price. This is the hook for external index feed adapters.
+    pub fn set_index_price(&mut self, price: Decimal) {
+        self.index_price = Some(price);
+    }
+
+    /// Returns the last traded price.
+    pub fn last_price(&self) -> Option<Decimal> {
+        self.last_price
+    }
+
+    /// Returns the mark price: the book mid if both sides are quoted, otherwise the last trade.
+    pub fn mark_price(&self) -> Option<Decimal> {
+        self.mid_price.or(self.last_price)
+    }
+
+    /// Returns the latest external index price.
+    pub fn index_price(&self) -> Option<Decimal> {
+        self.index_price
+    }
+
+    /// Returns the reference price a trigger of type `trigger_by` is evaluated against.
+    pub fn price_for(&self, trigger_by: TriggerType) -> Option<Decimal> {
+        match trigger_by {
+            TriggerType::LastPrice => self.last_price(),
+            TriggerType::MarkPrice => self.mark_price(),
+            TriggerType::IndexPrice => self.index_price(),
+        }
+    }
+
+    /// Returns true if a conditional order should activate at the current reference prices.
+    ///
+    /// # Arguments
+    /// * `order` - A Stop/StopLimit order; orders without a `trigger_price` never trigger
+    ///
+    /// # Notes
+    /// - `trigger_by` defaults to `LastPrice` when unset
+    /// - Buy orders trigger when the reference price rises to or above the trigger price,
+    ///   sell orders when it falls to or below it
+    /// - Returns false while the selected reference price is unknown
+    pub fn is_triggered(&self, order: &Order) -> bool {
+        let trigger_price = match order.trigger_price {
+            Some(price) => price,
+            None => return false,
+        };
+        let reference = match self.price_for(order.trigger_by.unwrap_or(TriggerType::LastPrice)) {
+            Some(price) => price,
+            None => return false,
+        };
+
+        match order.side {
+            Side::Bid => reference >= trigger_price,
+            Side::Ask => reference <= trigger_price,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::types::{OrderType, OrderBuilder};
+
+    fn order_builder(side: Side, instrument_id: Uuid) -> OrderBuilder {
+        Order::builder().account_id(Uuid::new_v4()).instrument_id(instrument_id).side(side).base_amount(dec!(1))
+    }
+
+    /// Stop order; `trigger_by` of None leaves the builder's `LastPrice` default.
+    fn create_stop_order(side: Side, trigger_price: Decimal, trigger_by: Option<TriggerType>) -> Order {
+        let builder = order_builder(side, Uuid::new_v4()).order_type(OrderType::Stop).trigger_price(trigger_price);
+        let builder = match trigger_by {
+            Some(trigger_by) => builder.trigger_by(trigger_by),
+            None => builder,
+        };
+        builder.build().expect("test order is valid")
+    }
+
+    fn create_limit_order(side: Side, price: Decimal, instrument_id: Uuid) -> Order {
+        order_builder(side, instrument_id).order_type(OrderType::Limit).limit_price(price).build().expect("test order is valid")
+    }
+
+    fn create_trade(price: Decimal) -> Trade {
+        Trade {
+            id: Uuid::new_v4(),
+            instrument_id: Uuid::new_v4(),
+            maker_order_id: Uuid::new_v4(),
+            taker_order_id: Uuid::new_v4(),
+            maker_account_id: Uuid::new_v4(),
+            taker_account_id: Uuid::new_v4(),
+            taker_side: Side::Bid,
+            base_amount: dec!(1),
+            quote_amount: price,
+            price,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_mark_prefers_mid_over_last() {
+        let instrument_id = Uuid::new_v4();
+        let mut book = OrderBook::new(instrument_id);
+        let mut prices = ReferencePrices::new();
+        assert_eq!(prices.mark_price(), None);
+
+        prices.on_trades(&[create_trade(dec!(100)), create_trade(dec!(101))]);
+        prices.update_from_book(&book);
+        assert_eq!(prices.last_price(), Some(dec!(101)));
+        assert_eq!(prices.mark_price(), Some(dec!(101)));
+
+        book.add_order(create_limit_order(Side::Bid, dec!(98), instrument_id));
+        book.add_order(create_limit_order(Side::Ask, dec!(99), instrument_id));
+
+        prices.update_from_book(&book);
+        assert_eq!(prices.mark_price(), Some(dec!(98.5)));
+    }
+
+    #[test]
+    fn test_trigger_direction_by_side() {
+        let mut prices = ReferencePrices::new();
+        let buy_stop = create_stop_order(Side::Bid, dec!(105), None);
+        let sell_stop = create_stop_order(Side::Ask, dec!(95), None);
+
+        // Nothing traded yet
+        assert!(!prices.is_triggered(&buy_stop));
+        assert!(!prices.is_triggered(&sell_stop));
+
+        prices.on_trades(&[create_trade(dec!(100))]);
+        assert!(!prices.is_triggered(&buy_stop));
+        assert!(!prices.is_triggered(&sell_stop));
+
+        prices.on_trades(&[create_trade(dec!(105))]);
+        assert!(prices.is_triggered(&buy_stop));
+
+        prices.on_trades(&[create_trade(dec!(95))]);
+        assert!(prices.is_triggered(&sell_stop));
+        assert!(!prices.is_triggered(&buy_stop));
+    }
+
+    #[test]
+    fn test_trigger_by_selects_price() {
+        let mut prices = ReferencePrices::new();
+        prices.on_trades(&[create_trade(dec!(100))]);
+
+        let by_last = create_stop_order(Side::Bid, dec!(100), Some(TriggerType::LastPrice));
+        let by_mark = create_stop_order(Side::Bid, dec!(100), Some(TriggerType::MarkPrice));
+        let by_index = create_stop_order(Side::Bid, dec!(100), Some(TriggerType::IndexPrice));
+
+        // Mark falls back to the last trade; index is unknown until the feed reports
+        assert!(prices.is_triggered(&by_last));
+        assert!(prices.is_triggered(&by_mark));
+        assert!(!prices.is_triggered(&by_index));
+
+        prices.set_index_price(dec!(99));
+        assert!(!prices.is_triggered(&by_index));
+        prices.set_index_price(dec!(100.5));
+        assert!(prices.is_triggered(&by_index));
+        assert_eq!(prices.price_for(TriggerType::IndexPrice), Some(dec!(100.5)));
+    }
+}
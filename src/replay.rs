@@ -0,0 +1,428 @@
+//--------------------------------------------------------------------------------------------------
+// MODULE OVERVIEW
+//--------------------------------------------------------------------------------------------------
+// This module replays a CSV of historical order events through a fresh MatchingEngine and checks
+// the resulting trades against a recorded trade log. It backs the `replay` binary and is the
+// building block for regression and recovery testing.
+//
+// Orders CSV (header optional):
+//   timestamp_ms,action,order_id,account_id,side,order_type,price,quantity,time_in_force
+//   - action is `place` or `cancel`; cancel rows only need timestamp_ms and order_id
+//   - side is `bid`/`ask`, order_type `limit`/`market`, time_in_force `gtc`/`ioc`/`gtx`
+//   - price is empty for market orders
+//
+// Trades CSV (header optional):
+//   taker_order_id,maker_order_id,price,base_amount
+//
+//--------------------------------------------------------------------------------------------------
+// STRUCTS
+//--------------------------------------------------------------------------------------------------
+// | Name            | Description                                      | Key Methods              |
+// |-----------------|--------------------------------------------------|--------------------------|
+// | ReplayEvent     | One timestamped order command                    |                          |
+// | RecordedTrade   | Trade fields compared during verification        | to_csv_row               |
+// | TradeMismatch   | First point where replayed and recorded differ   |                          |
+// | ReplayReport    | Counts and mismatches of a completed replay      | is_consistent            |
+//
+//--------------------------------------------------------------------------------------------------
+// ENUMS
+//--------------------------------------------------------------------------------------------------
+// | Name            | Description                                      | Variants                 |
+// |-----------------|--------------------------------------------------|--------------------------|
+// | ReplayCommand   | Command applied to the engine                    | Place, Cancel            |
+// | ReplaySpeed     | Pacing between events                            | AsFastAsPossible         |
+// |                 |                                                  | RealTime                 |
+// | ReplayError     | Errors raised while reading input or replaying   | Parse                    |
+// |                 |                                                  | InvalidSpeed             |
+//
+//--------------------------------------------------------------------------------------------------
+// FUNCTIONS
+//--------------------------------------------------------------------------------------------------
+// | Name              | Description                                      | Return Type            |
+// |-------------------|--------------------------------------------------|------------------------|
+// | parse_orders_csv  | Parses the orders CSV into replay events         | Result<Vec<ReplayEv..>>|
+// | parse_trades_csv  | Parses a recorded trade log                      | Result<Vec<Recorded..>>|
+// | replay            | Runs events through an engine and verifies       | Result<ReplayReport>   |
+//
+//--------------------------------------------------------------------------------------------------
+// TESTS
+//--------------------------------------------------------------------------------------------------
+// | Name                                  | Description                                       |
+// |---------------------------------------|---------------------------------------------------|
+// | test_parse_orders_csv                 | Header, place and cancel rows are parsed          |
+// | test_parse_reports_line_number        | Malformed rows fail with their 1-based line       |
+// | test_replay_matches_recorded_trades   | Replaying a session reproduces its trade log      |
+// | test_replay_reports_first_divergence  | A differing trade log is reported as a mismatch   |
+// | test_replay_rejects_invalid_speed     | Non-positive or non-finite factors are an error   |
+//--------------------------------------------------------------------------------------------------
+
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+use chrono::DateTime;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::clock::ManualClock;
+use crate::matching_engine::MatchingEngine;
+use crate::types::{Order, Side, OrderType, Trade, TimeInForce};
+
+/// Errors raised while reading replay input or starting a replay.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    /// A CSV row could not be parsed.
+    #[error("Line {line}: {message}")]
+    Parse { line: usize, message: String },
+    /// A real-time pacing factor was not positive and finite.
+    #[error("Replay speed must be positive and finite, got {0}")]
+    InvalidSpeed(f64),
+}
+
+/// A command applied to the engine during replay.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayCommand {
+    /// Submit a new order.
+    Place(Box<Order>, TimeInForce),
+    /// Cancel a resting order.
+    Cancel(Uuid),
+}
+
+/// One timestamped command from the orders CSV.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayEvent {
+    /// Original arrival time in milliseconds, used for real-time pacing.
+    pub timestamp_ms: u64,
+    /// The command to apply.
+    pub command: ReplayCommand,
+}
+
+/// Pacing between replayed events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Apply events back to back.
+    AsFastAsPossible,
+    /// Preserve the recorded gaps between events, scaled by this factor (2.0 = twice as fast).
+    /// The factor must be positive and finite.
+    RealTime(f64),
+}
+
+/// The trade fields compared during verification.
+/// Trade IDs and timestamps are generated on every run and are deliberately excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedTrade {
+    /// ID of the aggressing order.
+    pub taker_order_id: Uuid,
+    /// ID of the resting order.
+    pub maker_order_id: Uuid,
+    /// Execution price.
+    pub price: Decimal,
+    /// Quantity traded in base units.
+    pub base_amount: Decimal,
+}
+
+impl RecordedTrade {
+    /// Formats the trade as a row of the trades CSV.
+    pub fn to_csv_row(&self) -> String {
+        format!("{},{},{},{}", self.taker_order_id, self.maker_order_id, self.price, self.base_amount)
+    }
+}
+
+impl From<&Trade> for RecordedTrade {
+    fn from(trade: &Trade) -> Self {
+        Self {
+            taker_order_id: trade.taker_order_id,
+            maker_order_id: trade.maker_order_id,
+            price: trade.price,
+            base_amount: trade.base_amount,
+        }
+    }
+}
+
+/// The first trade at which the replay diverged from the recorded log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeMismatch {
+    /// Position of the trade in the log.
+    pub index: usize,
+    /// The recorded trade, or None if the replay produced extra trades.
+    pub expected: Option<RecordedTrade>,
+    /// The replayed trade, or None if the replay produced fewer trades.
+    pub actual: Option<RecordedTrade>,
+}
+
+/// Outcome of a completed replay.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Number of events applied.
+    pub events: usize,
+    /// Number of events the engine rejected.
+    pub rejected: usize,
+    /// Trades produced by the replay, in execution order.
+    pub trades: Vec<RecordedTrade>,
+    /// First divergence from the recorded log, if one was supplied and differs.
+    pub mismatch: Option<TradeMismatch>,
+}
+
+impl ReplayReport {
+    /// Returns true if the replayed trades match the recorded log.
+    pub fn is_consistent(&self) -> bool {
+        self.mismatch.is_none()
+    }
+}
+
+/// Parses the orders CSV into replay events for `instrument_id`.
+///
+/// # Notes
+/// - Blank lines and a leading header row are skipped
+/// - Errors carry the 1-based line number of the offending row
+pub fn parse_orders_csv(input: &str, instrument_id: Uuid) -> Result<Vec<ReplayEvent>, ReplayError> {
+    data_rows(input, "timestamp_ms")
+        .map(|(line, fields)| parse_order_row(&fields, instrument_id).map_err(|message| ReplayError::Parse { line, message }))
+        .collect()
+}
+
+/// Parses a recorded trades CSV.
+pub fn parse_trades_csv(input: &str) -> Result<Vec<RecordedTrade>, ReplayError> {
+    data_rows(input, "taker_order_id")
+        .map(|(line, fields)| parse_trade_row(&fields).map_err(|message| ReplayError::Parse { line, message }))
+        .collect()
+}
+
+/// Replays `events` through a fresh engine and compares the trades against `recorded`.
+///
+/// # Arguments
+/// * `events` - Commands in arrival order
+/// * `instrument_id` - Instrument of the fresh engine; must match the orders' instrument
+/// * `speed` - Pacing between events
+/// * `recorded` - The expected trade log, or None to only collect trades
+///
+/// # Returns
+/// The report, or `ReplayError::InvalidSpeed` if a `RealTime` factor is not positive and finite
+///
+/// # Notes
+/// - Engine rejections are counted, not fatal: they are part of the recorded history
+/// - The engine runs on a `ManualClock` set to each event's recorded time, so replayed trades
+///   carry the original timestamps regardless of pacing
+pub fn replay(
+    events: Vec<ReplayEvent>,
+    instrument_id: Uuid,
+    speed: ReplaySpeed,
+    recorded: Option<&[RecordedTrade]>,
+) -> Result<ReplayReport, ReplayError> {
+    if let ReplaySpeed::RealTime(factor) = speed
+        && !(factor > 0.0 && factor.is_finite())
+    {
+        return Err(ReplayError::InvalidSpeed(factor));
+    }
+
+    let clock = ManualClock::new(DateTime::UNIX_EPOCH);
+    let mut engine = MatchingEngine::with_time_source(instrument_id, clock.clone());
+    let mut report = ReplayReport::default();
+    let mut previous_timestamp = None;
+
+    for event in events {
+        if let (ReplaySpeed::RealTime(factor), Some(previous)) = (speed, previous_timestamp) {
+            let gap_ms = event.timestamp_ms.saturating_sub(previous) as f64 / factor;
+            thread::sleep(Duration::from_secs_f64(gap_ms / 1000.0));
+        }
+        previous_timestamp = Some(event.timestamp_ms);
+        // Timestamps beyond chrono's range are left at the previous event's time
+        if let Some(now) = i64::try_from(event.timestamp_ms).ok().and_then(DateTime::from_timestamp_millis) {
+            clock.set(now);
+        }
+
+        let is_accepted = match event.command {
+            ReplayCommand::Place(order, time_in_force) => match engine.process_order(*order, time_in_force) {
+                Ok(result) => {
+                    report.trades.extend(result.trades.iter().map(RecordedTrade::from));
+                    true
+                }
+                Err(_) => false,
+            },
+            ReplayCommand::Cancel(order_id) => engine.cancel_order(order_id).is_ok(),
+        };
+        report.events += 1;
+        if !is_accepted {
+            report.rejected += 1;
+        }
+    }
+
+    if let Some(recorded) = recorded {
+        report.mismatch = first_mismatch(recorded, &report.trades);
+    }
+    Ok(report)
+}
+
+/// Returns the first index where `expected` and `actual` differ, including a length difference.
+fn first_mismatch(expected: &[RecordedTrade], actual: &[RecordedTrade]) -> Option<TradeMismatch> {
+    (0..expected.len().max(actual.len()))
+        .map(|index| TradeMismatch {
+            index,
+            expected: expected.get(index).copied(),
+            actual: actual.get(index).copied(),
+        })
+        .find(|mismatch| mismatch.expected != mismatch.actual)
+}
+
+/// Yields (line number, trimmed fields) for every non-blank row, skipping a header that starts
+/// with `header_start`.
+fn data_rows<'a>(input: &'a str, header_start: &'a str) -> impl Iterator<Item = (usize, Vec<&'a str>)> + 'a {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, row)| !row.trim().is_empty())
+        .filter(move |(index, row)| !(*index == 0 && row.trim_start().starts_with(header_start)))
+        .map(|(index, row)| (index + 1, row.split(',').map(str::trim).collect()))
+}
+
+fn parse_order_row(fields: &[&str], instrument_id: Uuid) -> Result<ReplayEvent, String> {
+    let timestamp_ms = parse_field(fields.first().copied().unwrap_or_default(), "timestamp_ms")?;
+    let action = fields.get(1).copied().unwrap_or_default();
+    let order_id = parse_field(fields.get(2).copied().unwrap_or_default(), "order_id")?;
+
+    let command = match action {
+        "cancel" => ReplayCommand::Cancel(order_id),
+        "place" => {
+            let [_, _, _, account_id, side, order_type, price, quantity, time_in_force] = fields else {
+                return Err(format!("expected 9 fields for place, found {}", fields.len()));
+            };
+            let side = match *side {
+                "bid" => Side::Bid,
+                "ask" => Side::Ask,
+                other => return Err(format!("unknown side '{}'", other)),
+            };
+            let order_type = match *order_type {
+                "limit" => OrderType::Limit,
+                "market" => OrderType::Market,
+                other => return Err(format!("unsupported order_type '{}'", other)),
+            };
+            let time_in_force = match *time_in_force {
+                "gtc" => TimeInForce::GTC,
+                "ioc" => TimeInForce::IOC,
+                "gtx" => TimeInForce::GTX,
+                other => return Err(format!("unknown time_in_force '{}'", other)),
+            };
+            let mut builder = Order::builder()
+                .id(order_id)
+                .account_id(parse_field(account_id, "account_id")?)
+                .instrument_id(instrument_id)
+                .side(side)
+                .order_type(order_type)
+                .base_amount(parse_field(quantity, "quantity")?);
+            if !price.is_empty() {
+                builder = builder.limit_price(parse_field(price, "price")?);
+            }
+            let order = builder.build().map_err(|e| e.to_string())?;
+            ReplayCommand::Place(Box::new(order), time_in_force)
+        }
+        other => return Err(format!("unknown action '{}'", other)),
+    };
+
+    Ok(ReplayEvent { timestamp_ms, command })
+}
+
+fn parse_trade_row(fields: &[&str]) -> Result<RecordedTrade, String> {
+    let [taker, maker, price, base_amount] = fields else {
+        return Err(format!("expected 4 fields, found {}", fields.len()));
+    };
+    Ok(RecordedTrade {
+        taker_order_id: parse_field(taker, "taker_order_id")?,
+        maker_order_id: parse_field(maker, "maker_order_id")?,
+        price: parse_field(price, "price")?,
+        base_amount: parse_field(base_amount, "base_amount")?,
+    })
+}
+
+fn parse_field<T: FromStr>(value: &str, name: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid {} '{}'", name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const MAKER_ID: &str = "00000000-0000-0000-0000-000000000001";
+    const TAKER_ID: &str = "00000000-0000-0000-0000-000000000002";
+    const ACCOUNT_ID: &str = "00000000-0000-0000-0000-0000000000aa";
+
+    fn session() -> String {
+        format!(
+            "timestamp_ms,action,order_id,account_id,side,order_type,price,quantity,time_in_force\n\
+             1000,place,{MAKER_ID},{ACCOUNT_ID},ask,limit,100.5,2,gtc\n\
+             1005,place,{TAKER_ID},{ACCOUNT_ID},bid,market,,1.5,ioc\n\
+             \n\
+             1010,cancel,{MAKER_ID}\n"
+        )
+    }
+
+    #[test]
+    fn test_parse_orders_csv() {
+        let instrument_id = Uuid::new_v4();
+        let events = parse_orders_csv(&session(), instrument_id).unwrap();
+
+        assert_eq!(events.len(), 3);
+        let ReplayCommand::Place(maker, tif) = &events[0].command else { panic!("expected place") };
+        assert_eq!(maker.id, Uuid::parse_str(MAKER_ID).unwrap());
+        assert_eq!(maker.instrument_id, instrument_id);
+        assert_eq!(maker.limit_price, Some(dec!(100.5)));
+        assert_eq!(maker.remaining_quote, dec!(201.0));
+        assert_eq!(*tif, TimeInForce::GTC);
+
+        let ReplayCommand::Place(taker, _) = &events[1].command else { panic!("expected place") };
+        assert_eq!(taker.order_type, OrderType::Market);
+        assert_eq!(taker.limit_price, None);
+
+        assert_eq!(events[2].timestamp_ms, 1010);
+        assert_eq!(events[2].command, ReplayCommand::Cancel(Uuid::parse_str(MAKER_ID).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_reports_line_number() {
+        let input = format!("1000,place,{MAKER_ID},{ACCOUNT_ID},ask,limit,100,2,gtc\n1001,place,{TAKER_ID},{ACCOUNT_ID},buy,limit,100,2,gtc");
+        let err = parse_orders_csv(&input, Uuid::new_v4()).unwrap_err();
+        assert_eq!(err, ReplayError::Parse { line: 2, message: "unknown side 'buy'".to_string() });
+
+        let err = parse_trades_csv("taker_order_id,maker_order_id,price,base_amount\nnot-a-uuid,x,1,1").unwrap_err();
+        assert!(matches!(err, ReplayError::Parse { line: 2, .. }));
+    }
+
+    #[test]
+    fn test_replay_matches_recorded_trades() {
+        let instrument_id = Uuid::new_v4();
+        let recorded = parse_trades_csv(&format!("{TAKER_ID},{MAKER_ID},100.5,1.5\n")).unwrap();
+        let events = parse_orders_csv(&session(), instrument_id).unwrap();
+
+        let report = replay(events, instrument_id, ReplaySpeed::AsFastAsPossible, Some(&recorded)).unwrap();
+
+        assert_eq!(report.events, 3);
+        assert_eq!(report.rejected, 0);
+        assert_eq!(report.trades, recorded);
+        assert!(report.is_consistent());
+        assert_eq!(report.trades[0].to_csv_row(), format!("{TAKER_ID},{MAKER_ID},100.5,1.5"));
+    }
+
+    #[test]
+    fn test_replay_reports_first_divergence() {
+        let instrument_id = Uuid::new_v4();
+        let recorded = parse_trades_csv(&format!("{TAKER_ID},{MAKER_ID},100.5,1\n")).unwrap();
+        let events = parse_orders_csv(&session(), instrument_id).unwrap();
+
+        let report = replay(events, instrument_id, ReplaySpeed::AsFastAsPossible, Some(&recorded)).unwrap();
+
+        let mismatch = report.mismatch.expect("replay should diverge");
+        assert_eq!(mismatch.index, 0);
+        assert_eq!(mismatch.expected.map(|t| t.base_amount), Some(dec!(1)));
+        assert_eq!(mismatch.actual.map(|t| t.base_amount), Some(dec!(1.5)));
+    }
+
+    #[test]
+    fn test_replay_rejects_invalid_speed() {
+        let instrument_id = Uuid::new_v4();
+        for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let events = parse_orders_csv(&session(), instrument_id).unwrap();
+            let err = replay(events, instrument_id, ReplaySpeed::RealTime(factor), None).unwrap_err();
+            assert!(matches!(err, ReplayError::InvalidSpeed(f) if f.is_nan() || f == factor));
+        }
+    }
+}
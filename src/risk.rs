@@ -0,0 +1,931 @@
+//--------------------------------------------------------------------------------------------------
+// MODULE OVERVIEW
+//--------------------------------------------------------------------------------------------------
+// This module implements pre-trade risk checks that run before an order reaches the matching
+// engine. Checks implement the `RiskCheck` trait and are evaluated in order by a `RiskPipeline`;
+// the first rejection stops the pipeline.
+//
+// | Component      | Description                                                              |
+// |----------------|--------------------------------------------------------------------------|
+// | RiskCheck      | Trait for a single check; also the hook for external risk services      |
+// | RiskPipeline   | Ordered list of checks evaluated for each incoming order                |
+// | RiskLimits     | Static limits for an instrument, optionally overridden per account      |
+// | LimitCheck     | Default in-process check enforcing `RiskLimits`                         |
+// | RateLimitCheck | Per-account order and cancel rate limits with burst allowance           |
+// | ExposureCheck  | Per-account open order count and resting notional caps                  |
+// | RiskRejection  | Machine-readable reason an order was rejected                           |
+//
+//--------------------------------------------------------------------------------------------------
+// STRUCTS
+//--------------------------------------------------------------------------------------------------
+// | Name          | Description                                        | Key Methods              |
+// |---------------|----------------------------------------------------|--------------------------|
+// | RiskLimits    | Max size, max notional and price band              |                          |
+// | LimitCheck    | Enforces instrument limits with account overrides  | new, with_account_limits |
+// | RateLimit     | Sustained rate and burst allowance                 | new                      |
+// | RateLimitCheck| Per-account GCRA limiter for orders and cancels    | new                      |
+// | AccountUsage  | Open orders and resting notional of one account    |                          |
+// | ExposureCheck | Caps open orders and notional from engine results  | new, usage               |
+// | RiskPipeline  | Runs checks in order                               | with_check, check        |
+// |               |                                                    | check_cancel             |
+// |               |                                                    | check_amend              |
+// |               |                                                    | on_match_result          |
+// |               |                                                    | on_cancel                |
+//
+//--------------------------------------------------------------------------------------------------
+// TRAITS
+//--------------------------------------------------------------------------------------------------
+// | Name          | Description                                        | Methods                  |
+// |---------------|----------------------------------------------------|--------------------------|
+// | RiskCheck     | A single pre-trade check                           | check                    |
+// |               |                                                    | check_cancel             |
+// |               |                                                    | check_amend              |
+// |               |                                                    | on_match_result          |
+// |               |                                                    | on_cancel                |
+//
+//--------------------------------------------------------------------------------------------------
+// ENUMS
+//--------------------------------------------------------------------------------------------------
+// | Name          | Description                                        | Variants                 |
+// |---------------|----------------------------------------------------|--------------------------|
+// | RiskRejection | Why an order failed risk checks                    | MaxOrderSize             |
+// |               |                                                    | MaxNotional              |
+// |               |                                                    | PriceBand                |
+// |               |                                                    | OrderRateLimited         |
+// |               |                                                    | CancelRateLimited        |
+// |               |                                                    | MaxOpenOrders            |
+// |               |                                                    | MaxExposure              |
+// |               |                                                    | External                 |
+//
+//--------------------------------------------------------------------------------------------------
+// TESTS
+//--------------------------------------------------------------------------------------------------
+// | Name                                   | Description                                      |
+// |----------------------------------------|--------------------------------------------------|
+// | test_max_order_size                    | Rejects orders above the size limit              |
+// | test_max_notional_limit_and_market     | Notional uses limit price, or touch for market   |
+// | test_price_band_around_mid             | Rejects limit prices too far from the mid        |
+// | test_account_override                  | Account limits replace instrument limits         |
+// | test_pipeline_stops_at_first_rejection | Later checks are skipped after a rejection       |
+// | test_rate_limit_burst_then_refill      | Burst is allowed, then one request per interval  |
+// | test_rate_limit_is_per_account         | Accounts have independent allowances             |
+// | test_cancel_rate_limit                 | Cancels are limited separately from orders       |
+// | test_exposure_tracks_resting_orders    | Usage follows rests, fills and cancels           |
+// | test_exposure_caps                     | Rejects orders over count or notional caps       |
+// | test_exposure_ignores_ioc_at_cap       | IOC orders never rest, so caps do not apply      |
+// | test_amend_checked_against_caps        | Amends are held to size and exposure caps        |
+//--------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+use rust_decimal::Decimal;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::matching_engine::MatchResult;
+use crate::orderbook::OrderBook;
+use crate::types::{Order, OrderStatus, Side, TimeInForce};
+
+/// Machine-readable reasons an order can fail pre-trade risk checks.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RiskRejection {
+    /// The order's base quantity exceeds the configured maximum.
+    #[error("Order quantity {quantity} exceeds maximum {max}")]
+    MaxOrderSize {
+        /// Base quantity of the rejected order.
+        quantity: Decimal,
+        /// Configured maximum base quantity.
+        max: Decimal,
+    },
+
+    /// The order's notional value (quantity * price) exceeds the configured maximum.
+    #[error("Order notional {notional} exceeds maximum {max}")]
+    MaxNotional {
+        /// Estimated notional of the rejected order.
+        notional: Decimal,
+        /// Configured maximum notional.
+        max: Decimal,
+    },
+
+    /// The order's limit price is too far from the book's reference price.
+    #[error("Order price {price} outside band [{lower}, {upper}]")]
+    PriceBand {
+        /// Limit price of the rejected order.
+        price: Decimal,
+        /// Lowest acceptable price.
+        lower: Decimal,
+        /// Highest acceptable price.
+        upper: Decimal,
+    },
+
+    /// The account submitted orders faster than its configured rate.
+    #[error("Account {0} exceeded its order rate limit")]
+    OrderRateLimited(Uuid),
+
+    /// The account submitted cancels faster than its configured rate.
+    #[error("Account {0} exceeded its cancel rate limit")]
+    CancelRateLimited(Uuid),
+
+    /// Accepting the order would exceed the account's open order cap.
+    #[error("Account {account_id} already has the maximum of {max} open orders")]
+    MaxOpenOrders {
+        /// Account that owns the rejected order.
+        account_id: Uuid,
+        /// Configured maximum number of open orders.
+        max: usize,
+    },
+
+    /// Accepting the order would exceed the account's resting notional cap.
+    #[error("Order would raise resting notional to {exposure}, above maximum {max}")]
+    MaxExposure {
+        /// Resting notional the account would have if the order rested in full.
+        exposure: Decimal,
+        /// Configured maximum resting notional.
+        max: Decimal,
+    },
+
+    /// An external risk service rejected the order.
+    #[error("Rejected by external risk check: {0}")]
+    External(String),
+}
+
+/// A single pre-trade risk check.
+///
+/// Implement this trait to plug an external risk service into a `RiskPipeline`; the in-process
+/// `LimitCheck` is the default implementation. Checks take `&mut self` so they may keep state
+/// (e.g. per-account counters).
+pub trait RiskCheck {
+    /// Evaluates `order`, to be submitted with `time_in_force`, against the current state of `book`.
+    ///
+    /// # Errors
+    /// Returns the `RiskRejection` describing why the order must not be matched.
+    fn check(&mut self, order: &Order, time_in_force: TimeInForce, book: &OrderBook) -> Result<(), RiskRejection>;
+
+    /// Evaluates a cancel request from `account_id`. Accepts by default.
+    ///
+    /// # Errors
+    /// Returns the `RiskRejection` describing why the cancel must not be processed.
+    fn check_cancel(&mut self, _account_id: Uuid) -> Result<(), RiskRejection> {
+        Ok(())
+    }
+
+    /// Evaluates amending the resting `order` to `new_price` and/or `new_base_amount`, with the
+    /// same meaning as in `MatchingEngine::amend_order`. Accepts by default.
+    ///
+    /// # Errors
+    /// Returns the `RiskRejection` describing why the amendment must not be processed.
+    fn check_amend(
+        &mut self,
+        _order: &Order,
+        _new_price: Option<Decimal>,
+        _new_base_amount: Option<Decimal>,
+        _book: &OrderBook,
+    ) -> Result<(), RiskRejection> {
+        Ok(())
+    }
+
+    /// Observes the outcome of an order or amendment the engine processed. No-op by default.
+    fn on_match_result(&mut self, _result: &MatchResult) {}
+
+    /// Observes an order the engine cancelled. No-op by default.
+    fn on_cancel(&mut self, _order: &Order) {}
+}
+
+/// Static risk limits. `None` disables the corresponding check.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RiskLimits {
+    /// Maximum base quantity of a single order.
+    pub max_order_size: Option<Decimal>,
+    /// Maximum notional (quantity * price) of a single order.
+    pub max_notional: Option<Decimal>,
+    /// Maximum distance of a limit price from the reference price, as a fraction (0.05 = 5%).
+    pub price_band: Option<Decimal>,
+}
+
+/// Default in-process check enforcing `RiskLimits` for one instrument, with per-account overrides.
+#[derive(Debug, Clone, Default)]
+pub struct LimitCheck {
+    /// Limits applied to every account without an override
+    instrument_limits: RiskLimits,
+    /// Accounts whose limits replace the instrument limits
+    account_limits: HashMap<Uuid, RiskLimits>,
+}
+
+impl LimitCheck {
+    /// Creates a check applying `instrument_limits` to every account.
+    pub fn new(instrument_limits: RiskLimits) -> Self {
+        Self {
+            instrument_limits,
+            account_limits: HashMap::new(),
+        }
+    }
+
+    /// Replaces the instrument limits with `limits` for orders from `account_id`.
+    pub fn with_account_limits(mut self, account_id: Uuid, limits: RiskLimits) -> Self {
+        self.account_limits.insert(account_id, limits);
+        self
+    }
+
+    /// Returns the limits that apply to `account_id`.
+    pub fn limits_for(&self, account_id: Uuid) -> &RiskLimits {
+        self.account_limits.get(&account_id).unwrap_or(&self.instrument_limits)
+    }
+
+    /// Checks an order of `base_amount` at `limit_price` from `account_id` against its limits.
+    /// The price band is applied to `band_price` only, so callers can exempt an unchanged price.
+    fn check_limits(
+        &self,
+        account_id: Uuid,
+        side: Side,
+        base_amount: Decimal,
+        limit_price: Option<Decimal>,
+        band_price: Option<Decimal>,
+        book: &OrderBook,
+    ) -> Result<(), RiskRejection> {
+        let limits = self.limits_for(account_id);
+
+        if let Some(max) = limits.max_order_size
+            && base_amount > max
+        {
+            return Err(RiskRejection::MaxOrderSize { quantity: base_amount, max });
+        }
+
+        // Market orders have no price of their own; estimate notional at the opposite touch
+        let touch = match side {
+            Side::Bid => book.best_ask(),
+            Side::Ask => book.best_bid(),
+        };
+        if let Some(max) = limits.max_notional
+            && let Some(price) = limit_price.or(touch)
+        {
+            let notional = base_amount * price;
+            if notional > max {
+                return Err(RiskRejection::MaxNotional { notional, max });
+            }
+        }
+
+        if let Some(band) = limits.price_band
+            && let Some(price) = band_price
+            && let Some(reference) = reference_price(book)
+        {
+            let lower = reference * (Decimal::ONE - band);
+            let upper = reference * (Decimal::ONE + band);
+            if price < lower || price > upper {
+                return Err(RiskRejection::PriceBand { price, lower, upper });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RiskCheck for LimitCheck {
+    fn check(&mut self, order: &Order, _time_in_force: TimeInForce, book: &OrderBook) -> Result<(), RiskRejection> {
+        self.check_limits(order.account_id, order.side, order.base_amount, order.limit_price, order.limit_price, book)
+    }
+
+    fn check_amend(
+        &mut self,
+        order: &Order,
+        new_price: Option<Decimal>,
+        new_base_amount: Option<Decimal>,
+        book: &OrderBook,
+    ) -> Result<(), RiskRejection> {
+        // The band only applies to a new price; a resting price may since have drifted outside it
+        let band_price = new_price.filter(|&price| Some(price) != order.limit_price);
+        self.check_limits(
+            order.account_id,
+            order.side,
+            new_base_amount.unwrap_or(order.base_amount),
+            new_price.or(order.limit_price),
+            band_price,
+            book,
+        )
+    }
+}
+
+/// Reference price for price bands: the mid if both sides are quoted, otherwise the one touch.
+fn reference_price(book: &OrderBook) -> Option<Decimal> {
+    match (book.best_bid(), book.best_ask()) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+        (bid, ask) => bid.or(ask),
+    }
+}
+
+/// A sustained request rate with a burst allowance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Sustained requests allowed per second.
+    pub per_second: NonZeroU32,
+    /// Requests that may be sent back-to-back after a quiet period.
+    pub burst: NonZeroU32,
+}
+
+impl RateLimit {
+    /// Creates a limit of `per_second` sustained requests with `burst` back-to-back requests.
+    pub fn new(per_second: NonZeroU32, burst: NonZeroU32) -> Self {
+        Self { per_second, burst }
+    }
+
+    /// Time one request "costs" at the sustained rate.
+    fn emission_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.per_second.get()
+    }
+
+    /// How far ahead of real time the schedule may run before requests are refused.
+    fn burst_tolerance(&self) -> Duration {
+        self.emission_interval() * (self.burst.get() - 1)
+    }
+}
+
+/// Per-account order and cancel rate limiting.
+///
+/// Uses the generic cell rate algorithm: each account has a theoretical arrival time that moves
+/// forward by one emission interval per accepted request, and requests are refused while it runs
+/// further ahead of now than the burst allows. This is equivalent to a token bucket without
+/// floating-point refill arithmetic.
+#[derive(Debug, Clone)]
+pub struct RateLimitCheck {
+    /// Limit on new orders, if any
+    order_limit: Option<RateLimit>,
+    /// Limit on cancels, if any
+    cancel_limit: Option<RateLimit>,
+    /// Theoretical arrival time of the next order per account
+    order_schedule: HashMap<Uuid, Instant>,
+    /// Theoretical arrival time of the next cancel per account
+    cancel_schedule: HashMap<Uuid, Instant>,
+}
+
+impl RateLimitCheck {
+    /// Creates a check limiting orders and/or cancels per account. `None` disables that limit.
+    pub fn new(order_limit: Option<RateLimit>, cancel_limit: Option<RateLimit>) -> Self {
+        Self {
+            order_limit,
+            cancel_limit,
+            order_schedule: HashMap::new(),
+            cancel_schedule: HashMap::new(),
+        }
+    }
+
+    /// Records an order from `account_id` at `now`, returning false if it exceeds the limit.
+    fn try_order(&mut self, account_id: Uuid, now: Instant) -> bool {
+        match self.order_limit {
+            Some(limit) => try_acquire(&mut self.order_schedule, limit, account_id, now),
+            None => true,
+        }
+    }
+
+    /// Records a cancel from `account_id` at `now`, returning false if it exceeds the limit.
+    fn try_cancel(&mut self, account_id: Uuid, now: Instant) -> bool {
+        match self.cancel_limit {
+            Some(limit) => try_acquire(&mut self.cancel_schedule, limit, account_id, now),
+            None => true,
+        }
+    }
+}
+
+/// GCRA admission for one request; advances the account's schedule only when admitted.
+fn try_acquire(schedule: &mut HashMap<Uuid, Instant>, limit: RateLimit, account_id: Uuid, now: Instant) -> bool {
+    let next_arrival = schedule.get(&account_id).map_or(now, |&tat| tat.max(now));
+    if next_arrival.duration_since(now) > limit.burst_tolerance() {
+        return false;
+    }
+    schedule.insert(account_id, next_arrival + limit.emission_interval());
+    true
+}
+
+impl RiskCheck for RateLimitCheck {
+    fn check(&mut self, order: &Order, _time_in_force: TimeInForce, _book: &OrderBook) -> Result<(), RiskRejection> {
+        if self.try_order(order.account_id, Instant::now()) {
+            Ok(())
+        } else {
+            Err(RiskRejection::OrderRateLimited(order.account_id))
+        }
+    }
+
+    fn check_cancel(&mut self, account_id: Uuid) -> Result<(), RiskRejection> {
+        if self.try_cancel(account_id, Instant::now()) {
+            Ok(())
+        } else {
+            Err(RiskRejection::CancelRateLimited(account_id))
+        }
+    }
+}
+
+/// Open order count and resting notional of one account on the instrument.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountUsage {
+    /// Number of orders currently resting on the book.
+    pub open_orders: usize,
+    /// Sum of `remaining_base * limit_price` over the resting orders.
+    pub gross_notional: Decimal,
+}
+
+/// Caps per-account open order count and resting notional on one instrument.
+///
+/// Usage is driven purely by engine outcomes (`on_match_result`, `on_cancel`), so an order that
+/// passes this check but is rejected later in the pipeline leaves no trace.
+#[derive(Debug, Clone, Default)]
+pub struct ExposureCheck {
+    /// Cap on resting orders per account, if any
+    max_open_orders: Option<usize>,
+    /// Cap on resting notional per account, if any
+    max_gross_notional: Option<Decimal>,
+    /// Resting orders: order_id -> (account_id, resting notional)
+    resting: HashMap<Uuid, (Uuid, Decimal)>,
+    /// Aggregated usage per account
+    usage: HashMap<Uuid, AccountUsage>,
+}
+
+impl ExposureCheck {
+    /// Creates a check with the given caps. `None` disables that cap.
+    pub fn new(max_open_orders: Option<usize>, max_gross_notional: Option<Decimal>) -> Self {
+        Self {
+            max_open_orders,
+            max_gross_notional,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the current usage of `account_id`.
+    pub fn usage(&self, account_id: Uuid) -> AccountUsage {
+        self.usage.get(&account_id).copied().unwrap_or_default()
+    }
+
+    /// Sets the resting notional of `order_id`, or forgets it when `notional` is `None`.
+    fn set_resting(&mut self, order_id: Uuid, account_id: Uuid, notional: Option<Decimal>) {
+        if let Some((owner, previous)) = self.resting.remove(&order_id)
+            && let Some(usage) = self.usage.get_mut(&owner)
+        {
+            usage.open_orders -= 1;
+            usage.gross_notional -= previous;
+            if usage.open_orders == 0 {
+                self.usage.remove(&owner);
+            }
+        }
+
+        if let Some(notional) = notional {
+            self.resting.insert(order_id, (account_id, notional));
+            let usage = self.usage.entry(account_id).or_default();
+            usage.open_orders += 1;
+            usage.gross_notional += notional;
+        }
+    }
+}
+
+impl RiskCheck for ExposureCheck {
+    fn check(&mut self, order: &Order, time_in_force: TimeInForce, _book: &OrderBook) -> Result<(), RiskRejection> {
+        // Only priced orders that may rest add exposure; IOC orders can only reduce it
+        let price = match (order.limit_price, time_in_force) {
+            (_, TimeInForce::IOC) | (None, _) => return Ok(()),
+            (Some(price), _) => price,
+        };
+        let usage = self.usage(order.account_id);
+
+        if let Some(max) = self.max_open_orders
+            && usage.open_orders >= max
+        {
+            return Err(RiskRejection::MaxOpenOrders { account_id: order.account_id, max });
+        }
+
+        if let Some(max) = self.max_gross_notional {
+            let exposure = usage.gross_notional + order.base_amount * price;
+            if exposure > max {
+                return Err(RiskRejection::MaxExposure { exposure, max });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_amend(
+        &mut self,
+        order: &Order,
+        new_price: Option<Decimal>,
+        new_base_amount: Option<Decimal>,
+        _book: &OrderBook,
+    ) -> Result<(), RiskRejection> {
+        let (Some(max), Some(price)) = (self.max_gross_notional, new_price.or(order.limit_price)) else {
+            return Ok(());
+        };
+        // An amend keeps the order count unchanged; only the change in resting notional matters
+        let remaining = new_base_amount.map_or(order.remaining_base, |base| base - order.filled_base);
+        let current = self.resting.get(&order.id).map_or(Decimal::ZERO, |&(_, notional)| notional);
+        let delta = remaining * price - current;
+        if delta <= Decimal::ZERO {
+            return Ok(());
+        }
+
+        let exposure = self.usage(order.account_id).gross_notional + delta;
+        if exposure > max {
+            return Err(RiskRejection::MaxExposure { exposure, max });
+        }
+        Ok(())
+    }
+
+    fn on_match_result(&mut self, result: &MatchResult) {
+        for fill in &result.fills {
+            let notional = match fill.maker_status {
+                OrderStatus::Filled => None,
+                _ => Some(fill.maker_remaining_base * fill.price),
+            };
+            self.set_resting(fill.maker_order_id, fill.maker_account_id, notional);
+        }
+
+        if let Some(order) = &result.processed_order {
+            // New/PartiallyFilled after processing means the order is now resting on the book
+            let notional = match (order.status, order.limit_price) {
+                (OrderStatus::New | OrderStatus::PartiallyFilled, Some(price)) => Some(order.remaining_base * price),
+                _ => None,
+            };
+            self.set_resting(order.id, order.account_id, notional);
+        }
+    }
+
+    fn on_cancel(&mut self, order: &Order) {
+        self.set_resting(order.id, order.account_id, None);
+    }
+}
+
+/// Ordered list of risk checks evaluated for each incoming order.
+#[derive(Default)]
+pub struct RiskPipeline {
+    /// Checks in evaluation order
+    checks: Vec<Box<dyn RiskCheck + Send>>,
+}
+
+impl RiskPipeline {
+    /// Creates an empty pipeline that accepts every order.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `check` to the end of the pipeline.
+    pub fn with_check(mut self, check: impl RiskCheck + Send + 'static) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// Runs every check in order, stopping at the first rejection.
+    ///
+    /// # Arguments
+    /// * `order` - The order about to be submitted to the matching engine
+    /// * `time_in_force` - Duration policy the order will be submitted with
+    /// * `book` - The order book the order will be matched against
+    ///
+    /// # Errors
+    /// Returns the first `RiskRejection` raised by a check.
+    pub fn check(&mut self, order: &Order, time_in_force: TimeInForce, book: &OrderBook) -> Result<(), RiskRejection> {
+        self.checks.iter_mut().try_for_each(|check| check.check(order, time_in_force, book))
+    }
+
+    /// Runs every check's cancel hook in order, stopping at the first rejection.
+    ///
+    /// # Arguments
+    /// * `account_id` - The account requesting the cancel
+    ///
+    /// # Errors
+    /// Returns the first `RiskRejection` raised by a check.
+    pub fn check_cancel(&mut self, account_id: Uuid) -> Result<(), RiskRejection> {
+        self.checks.iter_mut().try_for_each(|check| check.check_cancel(account_id))
+    }
+
+    /// Runs every check's amend hook in order, stopping at the first rejection.
+    ///
+    /// # Arguments
+    /// * `order` - The resting order about to be amended
+    /// * `new_price` - The new limit price, or `None` to keep the current one
+    /// * `new_base_amount` - The new total quantity, or `None` to keep the current one
+    /// * `book` - The order book the order rests on
+    ///
+    /// # Errors
+    /// Returns the first `RiskRejection` raised by a check.
+    pub fn check_amend(
+        &mut self,
+        order: &Order,
+        new_price: Option<Decimal>,
+        new_base_amount: Option<Decimal>,
+        book: &OrderBook,
+    ) -> Result<(), RiskRejection> {
+        self.checks
+            .iter_mut()
+            .try_for_each(|check| check.check_amend(order, new_price, new_base_amount, book))
+    }
+
+    /// Forwards the outcome of a processed order or amendment to every check.
+    pub fn on_match_result(&mut self, result: &MatchResult) {
+        self.checks.iter_mut().for_each(|check| check.on_match_result(result));
+    }
+
+    /// Forwards an order cancelled by the engine to every check.
+    pub fn on_cancel(&mut self, order: &Order) {
+        self.checks.iter_mut().for_each(|check| check.on_cancel(order));
+    }
+}
+
+impl std::fmt::Debug for RiskPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RiskPipeline").field("checks", &self.checks.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use crate::matching_engine::MatchingEngine;
+    use crate::types::OrderType;
+
+    /// Limit order at `price`, or a market order when `price` is None.
+    fn create_test_order(side: Side, price: Option<Decimal>, quantity: Decimal, instrument_id: Uuid) -> Order {
+        let builder = Order::builder()
+            .account_id(Uuid::new_v4())
+            .instrument_id(instrument_id)
+            .side(side)
+            .base_amount(quantity);
+        let builder = match price {
+            Some(price) => builder.order_type(OrderType::Limit).limit_price(price),
+            None => builder.order_type(OrderType::Market),
+        };
+        builder.build().expect("test order is valid")
+    }
+
+    /// Book quoted 99 @ 101 (mid 100).
+    fn quoted_book(instrument_id: Uuid) -> OrderBook {
+        let mut book = OrderBook::new(instrument_id);
+        book.add_order(create_test_order(Side::Bid, Some(dec!(99)), dec!(1), instrument_id));
+        book.add_order(create_test_order(Side::Ask, Some(dec!(101)), dec!(1), instrument_id));
+        book
+    }
+
+    #[test]
+    fn test_max_order_size() {
+        let instrument_id = Uuid::new_v4();
+        let book = OrderBook::new(instrument_id);
+        let mut check = LimitCheck::new(RiskLimits { max_order_size: Some(dec!(10)), ..RiskLimits::default() });
+
+        let ok = create_test_order(Side::Bid, Some(dec!(100)), dec!(10), instrument_id);
+        assert_eq!(check.check(&ok, TimeInForce::GTC, &book), Ok(()));
+
+        let too_big = create_test_order(Side::Bid, Some(dec!(100)), dec!(10.5), instrument_id);
+        assert_eq!(
+            check.check(&too_big, TimeInForce::GTC, &book),
+            Err(RiskRejection::MaxOrderSize { quantity: dec!(10.5), max: dec!(10) })
+        );
+    }
+
+    #[test]
+    fn test_max_notional_limit_and_market() {
+        let instrument_id = Uuid::new_v4();
+        let book = quoted_book(instrument_id);
+        let mut check = LimitCheck::new(RiskLimits { max_notional: Some(dec!(1000)), ..RiskLimits::default() });
+
+        let limit = create_test_order(Side::Ask, Some(dec!(100)), dec!(11), instrument_id);
+        assert_eq!(
+            check.check(&limit, TimeInForce::GTC, &book),
+            Err(RiskRejection::MaxNotional { notional: dec!(1100), max: dec!(1000) })
+        );
+
+        // A market buy is valued at the best ask
+        let market = create_test_order(Side::Bid, None, dec!(10), instrument_id);
+        assert_eq!(
+            check.check(&market, TimeInForce::GTC, &book),
+            Err(RiskRejection::MaxNotional { notional: dec!(1010), max: dec!(1000) })
+        );
+    }
+
+    #[test]
+    fn test_price_band_around_mid() {
+        let instrument_id = Uuid::new_v4();
+        let mut check = LimitCheck::new(RiskLimits { price_band: Some(dec!(0.05)), ..RiskLimits::default() });
+
+        // Empty book: nothing to anchor the band to
+        let far = create_test_order(Side::Bid, Some(dec!(50)), dec!(1), instrument_id);
+        assert_eq!(check.check(&far, TimeInForce::GTC, &OrderBook::new(instrument_id)), Ok(()));
+
+        let book = quoted_book(instrument_id);
+        let inside = create_test_order(Side::Bid, Some(dec!(105)), dec!(1), instrument_id);
+        assert_eq!(check.check(&inside, TimeInForce::GTC, &book), Ok(()));
+        assert_eq!(
+            check.check(&far, TimeInForce::GTC, &book),
+            Err(RiskRejection::PriceBand { price: dec!(50), lower: dec!(95.00), upper: dec!(105.00) })
+        );
+    }
+
+    #[test]
+    fn test_account_override() {
+        let instrument_id = Uuid::new_v4();
+        let book = OrderBook::new(instrument_id);
+        let order = create_test_order(Side::Bid, Some(dec!(100)), dec!(50), instrument_id);
+
+        let mut check = LimitCheck::new(RiskLimits { max_order_size: Some(dec!(10)), ..RiskLimits::default() })
+            .with_account_limits(order.account_id, RiskLimits { max_order_size: Some(dec!(100)), ..RiskLimits::default() });
+        assert_eq!(check.check(&order, TimeInForce::GTC, &book), Ok(()));
+
+        let other = create_test_order(Side::Bid, Some(dec!(100)), dec!(50), instrument_id);
+        assert!(matches!(check.check(&other, TimeInForce::GTC, &book), Err(RiskRejection::MaxOrderSize { .. })));
+    }
+
+    #[test]
+    fn test_pipeline_stops_at_first_rejection() {
+        struct CountingCheck(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+        impl RiskCheck for CountingCheck {
+            fn check(&mut self, _order: &Order, _time_in_force: TimeInForce, _book: &OrderBook) -> Result<(), RiskRejection> {
+                self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(())
+            }
+        }
+
+        let instrument_id = Uuid::new_v4();
+        let book = OrderBook::new(instrument_id);
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut pipeline = RiskPipeline::new()
+            .with_check(LimitCheck::new(RiskLimits { max_order_size: Some(dec!(1)), ..RiskLimits::default() }))
+            .with_check(CountingCheck(calls.clone()));
+
+        let small = create_test_order(Side::Bid, Some(dec!(100)), dec!(1), instrument_id);
+        assert_eq!(pipeline.check(&small, TimeInForce::GTC, &book), Ok(()));
+        let large = create_test_order(Side::Bid, Some(dec!(100)), dec!(2), instrument_id);
+        assert!(pipeline.check(&large, TimeInForce::GTC, &book).is_err());
+
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 1);
+        assert_eq!(RiskPipeline::new().check(&large, TimeInForce::GTC, &book), Ok(()));
+    }
+
+    fn limit(per_second: u32, burst: u32) -> RateLimit {
+        match (NonZeroU32::new(per_second), NonZeroU32::new(burst)) {
+            (Some(per_second), Some(burst)) => RateLimit::new(per_second, burst),
+            _ => panic!("test limits must be non-zero"),
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_burst_then_refill() {
+        let account_id = Uuid::new_v4();
+        let mut check = RateLimitCheck::new(Some(limit(10, 3)), None);
+        let start = Instant::now();
+
+        // Three back-to-back orders fit the burst; the fourth does not
+        for _ in 0..3 {
+            assert!(check.try_order(account_id, start));
+        }
+        assert!(!check.try_order(account_id, start));
+
+        // One emission interval (100ms) later exactly one more is allowed
+        let later = start + Duration::from_millis(100);
+        assert!(check.try_order(account_id, later));
+        assert!(!check.try_order(account_id, later));
+
+        // After a long quiet period the full burst is available again
+        let idle = start + Duration::from_secs(5);
+        for _ in 0..3 {
+            assert!(check.try_order(account_id, idle));
+        }
+        assert!(!check.try_order(account_id, idle));
+    }
+
+    #[test]
+    fn test_rate_limit_is_per_account() {
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut check = RateLimitCheck::new(Some(limit(1, 1)), None);
+        let now = Instant::now();
+
+        assert!(check.try_order(first, now));
+        assert!(!check.try_order(first, now));
+        assert!(check.try_order(second, now));
+    }
+
+    #[test]
+    fn test_cancel_rate_limit() {
+        let instrument_id = Uuid::new_v4();
+        let book = OrderBook::new(instrument_id);
+        let order = create_test_order(Side::Bid, Some(dec!(100)), dec!(1), instrument_id);
+        let mut pipeline = RiskPipeline::new().with_check(RateLimitCheck::new(None, Some(limit(1, 1))));
+
+        // Orders are unlimited, cancels are not
+        assert_eq!(pipeline.check(&order, TimeInForce::GTC, &book), Ok(()));
+        assert_eq!(pipeline.check(&order, TimeInForce::GTC, &book), Ok(()));
+        assert_eq!(pipeline.check_cancel(order.account_id), Ok(()));
+        assert_eq!(
+            pipeline.check_cancel(order.account_id),
+            Err(RiskRejection::CancelRateLimited(order.account_id))
+        );
+    }
+
+    #[test]
+    fn test_exposure_tracks_resting_orders() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        let mut check = ExposureCheck::new(None, None);
+
+        let maker = create_test_order(Side::Ask, Some(dec!(100)), dec!(2), instrument_id);
+        let (maker_id, maker_account) = (maker.id, maker.account_id);
+        let result = engine.process_order(maker, TimeInForce::GTC).unwrap();
+        check.on_match_result(&result);
+        assert_eq!(check.usage(maker_account), AccountUsage { open_orders: 1, gross_notional: dec!(200) });
+
+        // Partial fill shrinks the maker's resting notional; the IOC taker never rests
+        let taker = create_test_order(Side::Bid, Some(dec!(100)), dec!(0.5), instrument_id);
+        let taker_account = taker.account_id;
+        let result = engine.process_order(taker, TimeInForce::IOC).unwrap();
+        check.on_match_result(&result);
+        assert_eq!(check.usage(maker_account), AccountUsage { open_orders: 1, gross_notional: dec!(150) });
+        assert_eq!(check.usage(taker_account), AccountUsage::default());
+
+        // Amending re-prices the resting exposure
+        let result = engine.amend_order(maker_id, Some(dec!(110)), None).unwrap();
+        check.on_match_result(&result);
+        assert_eq!(check.usage(maker_account).gross_notional, dec!(165));
+
+        let cancelled = engine.cancel_order(maker_id).unwrap().processed_order.unwrap();
+        check.on_cancel(&cancelled);
+        assert_eq!(check.usage(maker_account), AccountUsage::default());
+    }
+
+    #[test]
+    fn test_exposure_caps() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        let mut pipeline = RiskPipeline::new().with_check(ExposureCheck::new(Some(2), Some(dec!(350))));
+        let account_id = Uuid::new_v4();
+
+        let mut submit = |price: Decimal| {
+            let mut order = create_test_order(Side::Bid, Some(price), dec!(1), instrument_id);
+            order.account_id = account_id;
+            pipeline.check(&order, TimeInForce::GTC, engine.order_book())?;
+            let result = engine.process_order(order, TimeInForce::GTC).unwrap();
+            pipeline.on_match_result(&result);
+            Ok::<(), RiskRejection>(())
+        };
+
+        assert_eq!(submit(dec!(100)), Ok(()));
+        assert_eq!(submit(dec!(260)), Err(RiskRejection::MaxExposure { exposure: dec!(360), max: dec!(350) }));
+        assert_eq!(submit(dec!(200)), Ok(()));
+        assert_eq!(submit(dec!(1)), Err(RiskRejection::MaxOpenOrders { account_id, max: 2 }));
+    }
+
+    #[test]
+    fn test_exposure_ignores_ioc_at_cap() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        let mut pipeline = RiskPipeline::new().with_check(ExposureCheck::new(Some(1), Some(dec!(100))));
+
+        // The account is at both caps with a resting bid
+        let bid = create_test_order(Side::Bid, Some(dec!(100)), dec!(1), instrument_id);
+        let account_id = bid.account_id;
+        pipeline.check(&bid, TimeInForce::GTC, engine.order_book()).unwrap();
+        pipeline.on_match_result(&engine.process_order(bid, TimeInForce::GTC).unwrap());
+        let ask = create_test_order(Side::Ask, Some(dec!(101)), dec!(1), instrument_id);
+        engine.process_order(ask, TimeInForce::GTC).unwrap();
+
+        let mut sell = create_test_order(Side::Ask, Some(dec!(101)), dec!(1), instrument_id);
+        sell.account_id = account_id;
+        assert_eq!(
+            pipeline.check(&sell, TimeInForce::GTC, engine.order_book()),
+            Err(RiskRejection::MaxOpenOrders { account_id, max: 1 })
+        );
+
+        // An IOC order cannot rest, so it is never blocked by resting caps
+        let mut buy = create_test_order(Side::Bid, Some(dec!(101)), dec!(1), instrument_id);
+        buy.account_id = account_id;
+        assert_eq!(pipeline.check(&buy, TimeInForce::IOC, engine.order_book()), Ok(()));
+        pipeline.on_match_result(&engine.process_order(buy, TimeInForce::IOC).unwrap());
+        assert_eq!(pipeline.check(&sell, TimeInForce::IOC, engine.order_book()), Ok(()));
+    }
+
+    #[test]
+    fn test_amend_checked_against_caps() {
+        let instrument_id = Uuid::new_v4();
+        let mut engine = MatchingEngine::new(instrument_id);
+        let mut pipeline = RiskPipeline::new()
+            .with_check(LimitCheck::new(RiskLimits { max_order_size: Some(dec!(5)), ..RiskLimits::default() }))
+            .with_check(ExposureCheck::new(None, Some(dec!(300))));
+
+        // The account rests 200 of its 300 notional cap
+        let bid = create_test_order(Side::Bid, Some(dec!(100)), dec!(2), instrument_id);
+        pipeline.check(&bid, TimeInForce::GTC, engine.order_book()).unwrap();
+        let result = engine.process_order(bid, TimeInForce::GTC).unwrap();
+        pipeline.on_match_result(&result);
+        let resting = result.processed_order.unwrap();
+        let book = engine.order_book();
+
+        assert_eq!(
+            pipeline.check_amend(&resting, None, Some(dec!(6)), book),
+            Err(RiskRejection::MaxOrderSize { quantity: dec!(6), max: dec!(5) })
+        );
+        assert_eq!(
+            pipeline.check_amend(&resting, None, Some(dec!(4)), book),
+            Err(RiskRejection::MaxExposure { exposure: dec!(400), max: dec!(300) })
+        );
+        assert_eq!(
+            pipeline.check_amend(&resting, Some(dec!(160)), None, book),
+            Err(RiskRejection::MaxExposure { exposure: dec!(320), max: dec!(300) })
+        );
+
+        // Growing within the cap, or shrinking, is accepted
+        assert_eq!(pipeline.check_amend(&resting, Some(dec!(150)), None, book), Ok(()));
+        assert_eq!(pipeline.check_amend(&resting, Some(dec!(50)), Some(dec!(5)), book), Ok(()));
+        assert_eq!(pipeline.check_amend(&resting, None, Some(dec!(1)), book), Ok(()));
+    }
+}
@@ -22,6 +22,7 @@
 // | OrderStatus   | Represents the status of an order.        |
 // | TriggerType   | How a trigger price is evaluated.         |
 // | CreatedFrom   | Source of order creation.                 |
+// | Liquidity     | Maker or taker role in a trade.           |
 //--------------------------------------------------------------------------------------------------
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -80,23 +81,69 @@ pub enum OrderStatus {
     Cancelled,
     /// The order was partially filled and then cancelled.
     PartiallyFilledCancelled,
+    /// The order reached its `expiration_date` before being fully filled.
+    Expired,
+}
+
+impl OrderStatus {
+    /// Returns true if the order can no longer change status.
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::PartiallyFilledCancelled | OrderStatus::Expired
+        )
+    }
+
+    /// Returns true if `next` is a legal successor of this status.
+    ///
+    /// # Notes
+    /// - WaitingTrigger -> New is the trigger firing
+    /// - PartiallyFilled -> PartiallyFilled is a further partial fill
+    /// - Only New and PartiallyFilled orders can expire
+    /// - Terminal statuses have no successors
+    pub fn can_transition_to(self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+        matches!(
+            (self, next),
+            (WaitingTrigger, New | Cancelled)
+                | (New, PartiallyFilled | Filled | Cancelled | Expired)
+                | (PartiallyFilled, PartiallyFilled | Filled | PartiallyFilledCancelled | Expired)
+        )
+    }
+
+    /// Moves to `next`, rejecting transitions the order lifecycle does not allow.
+    ///
+    /// # Returns
+    /// `next` if the transition is legal, otherwise `TypeError::InvalidStatusTransition`
+    pub fn transition(self, next: OrderStatus) -> Result<OrderStatus, TypeError> {
+        if self.can_transition_to(next) {
+            Ok(next)
+        } else {
+            Err(TypeError::InvalidStatusTransition { from: self, to: next })
+        }
+    }
+
+    /// Moves to the cancelled status matching how much of the order was filled.
+    pub fn cancel(self) -> Result<OrderStatus, TypeError> {
+        match self {
+            OrderStatus::PartiallyFilled => self.transition(OrderStatus::PartiallyFilledCancelled),
+            _ => self.transition(OrderStatus::Cancelled),
+        }
+    }
 }
 
 /// Defines how long an order remains active in the order book.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[non_exhaustive]
 #[allow(dead_code)]
 pub enum TimeInForce {
     /// Good Till Cancel - remains active until explicitly cancelled
+    #[default]
     GTC,
     /// Immediate Or Cancel - must be filled immediately (fully or partially) or cancelled
     IOC,
-}
-
-impl Default for TimeInForce {
-    fn default() -> Self {
-        Self::GTC
-    }
+    /// Good Till Crossing - rests like GTC, but is cancelled instead of matched if it would cross
+    GTX,
 }
 
 /// Specifies the price type used to evaluate the trigger condition for conditional orders.
@@ -106,7 +153,19 @@ impl Default for TimeInForce {
 pub enum TriggerType {
     /// Trigger is evaluated against the last traded price.
     LastPrice,
-    // Add other types like MarkPrice, IndexPrice if needed later
+    /// Trigger is evaluated against the engine's mark price.
+    MarkPrice,
+    /// Trigger is evaluated against an external index price.
+    IndexPrice,
+}
+
+/// Whether an order provided liquidity to a trade or took it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Liquidity {
+    /// The order was resting on the book (added liquidity).
+    Maker,
+    /// The order crossed the book (removed liquidity).
+    Taker,
 }
 
 /// Indicates the origin system or interface that created the order.
@@ -128,7 +187,9 @@ pub enum CreatedFrom {
 // | Name          | Description                                   |
 // |---------------|-----------------------------------------------|
 // | Order         | Represents a trading order in the system.     |
+// | OrderBuilder  | Validating constructor for new Orders.        |
 // | Trade         | Represents a completed trade between orders.  |
+// | Fill          | Maker-side outcome of a single match.         |
 //--------------------------------------------------------------------------------------------------
 
 /// Represents a trading order, based on `@roxom.md`.
@@ -181,6 +242,176 @@ pub struct Order {
     pub sequence_id: u64,
 }
 
+impl Order {
+    /// Starts building a new order. See `OrderBuilder` for defaults and validation.
+    pub fn builder() -> OrderBuilder {
+        OrderBuilder::default()
+    }
+
+    /// Checks that the order's fields are consistent with its type.
+    ///
+    /// # Notes
+    /// - Limit/StopLimit orders need a positive limit price
+    /// - Stop/StopLimit orders need a positive trigger price
+    /// - The base amount must be positive and the expiry after creation
+    pub fn validate(&self) -> Result<(), TypeError> {
+        if self.base_amount <= Decimal::ZERO {
+            return Err(TypeError::NonPositiveAmount(self.base_amount));
+        }
+        if matches!(self.order_type, OrderType::Limit | OrderType::StopLimit) {
+            match self.limit_price {
+                None => return Err(TypeError::MissingLimitPrice(self.order_type)),
+                Some(price) if price <= Decimal::ZERO => return Err(TypeError::NonPositivePrice(price)),
+                Some(_) => {}
+            }
+        }
+        if matches!(self.order_type, OrderType::Stop | OrderType::StopLimit) {
+            match self.trigger_price {
+                None => return Err(TypeError::MissingTriggerPrice(self.order_type)),
+                Some(price) if price <= Decimal::ZERO => return Err(TypeError::NonPositivePrice(price)),
+                Some(_) => {}
+            }
+        }
+        if self.expiration_date <= self.created_at {
+            return Err(TypeError::ExpiryNotInFuture(self.expiration_date));
+        }
+        Ok(())
+    }
+}
+
+/// Builds a validated `Order`.
+///
+/// `account_id`, `instrument_id`, `side`, `order_type` and `base_amount` are required. Everything
+/// else defaults: a fresh `id`, no `ext_id`, expiry one year out, `CreatedFrom::Api`, and
+/// `trigger_by` of `LastPrice` for conditional orders. Status starts as `New`, or
+/// `WaitingTrigger` for Stop/StopLimit orders.
+#[derive(Debug, Clone, Default)]
+pub struct OrderBuilder {
+    id: Option<Uuid>,
+    ext_id: Option<String>,
+    account_id: Option<Uuid>,
+    instrument_id: Option<Uuid>,
+    side: Option<Side>,
+    order_type: Option<OrderType>,
+    limit_price: Option<Decimal>,
+    trigger_price: Option<Decimal>,
+    trigger_by: Option<TriggerType>,
+    base_amount: Option<Decimal>,
+    expiration_date: Option<DateTime<Utc>>,
+    created_from: Option<CreatedFrom>,
+}
+
+impl OrderBuilder {
+    /// Sets the order ID instead of generating one.
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the client-provided identifier.
+    pub fn ext_id(mut self, ext_id: impl Into<String>) -> Self {
+        self.ext_id = Some(ext_id.into());
+        self
+    }
+
+    /// Sets the owning account. Required.
+    pub fn account_id(mut self, account_id: Uuid) -> Self {
+        self.account_id = Some(account_id);
+        self
+    }
+
+    /// Sets the instrument traded. Required.
+    pub fn instrument_id(mut self, instrument_id: Uuid) -> Self {
+        self.instrument_id = Some(instrument_id);
+        self
+    }
+
+    /// Sets the order side. Required.
+    pub fn side(mut self, side: Side) -> Self {
+        self.side = Some(side);
+        self
+    }
+
+    /// Sets the order type. Required.
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = Some(order_type);
+        self
+    }
+
+    /// Sets the limit price (Limit/StopLimit).
+    pub fn limit_price(mut self, price: Decimal) -> Self {
+        self.limit_price = Some(price);
+        self
+    }
+
+    /// Sets the trigger price (Stop/StopLimit).
+    pub fn trigger_price(mut self, price: Decimal) -> Self {
+        self.trigger_price = Some(price);
+        self
+    }
+
+    /// Sets which reference price the trigger is evaluated against.
+    pub fn trigger_by(mut self, trigger_by: TriggerType) -> Self {
+        self.trigger_by = Some(trigger_by);
+        self
+    }
+
+    /// Sets the quantity in base units. Required.
+    pub fn base_amount(mut self, base_amount: Decimal) -> Self {
+        self.base_amount = Some(base_amount);
+        self
+    }
+
+    /// Sets the expiry.
+    pub fn expiration_date(mut self, expiration_date: DateTime<Utc>) -> Self {
+        self.expiration_date = Some(expiration_date);
+        self
+    }
+
+    /// Sets the origin of the order.
+    pub fn created_from(mut self, created_from: CreatedFrom) -> Self {
+        self.created_from = Some(created_from);
+        self
+    }
+
+    /// Builds the order and validates it with `Order::validate`.
+    ///
+    /// # Returns
+    /// The order, or `TypeError::MissingField` if a required setter was not called, or the
+    /// validation error.
+    pub fn build(self) -> Result<Order, TypeError> {
+        let order_type = self.order_type.ok_or(TypeError::MissingField("order_type"))?;
+        let base_amount = self.base_amount.ok_or(TypeError::MissingField("base_amount"))?;
+        let is_conditional = matches!(order_type, OrderType::Stop | OrderType::StopLimit);
+        let now = Utc::now();
+
+        let order = Order {
+            id: self.id.unwrap_or_else(Uuid::new_v4),
+            ext_id: self.ext_id,
+            account_id: self.account_id.ok_or(TypeError::MissingField("account_id"))?,
+            order_type,
+            instrument_id: self.instrument_id.ok_or(TypeError::MissingField("instrument_id"))?,
+            side: self.side.ok_or(TypeError::MissingField("side"))?,
+            limit_price: self.limit_price,
+            trigger_price: self.trigger_price,
+            base_amount,
+            remaining_quote: self.limit_price.map_or(Decimal::ZERO, |price| price * base_amount),
+            remaining_base: base_amount,
+            filled_quote: Decimal::ZERO,
+            filled_base: Decimal::ZERO,
+            expiration_date: self.expiration_date.unwrap_or(now + chrono::Duration::days(365)),
+            status: if is_conditional { OrderStatus::WaitingTrigger } else { OrderStatus::New },
+            created_at: now,
+            updated_at: now,
+            trigger_by: self.trigger_by.or(is_conditional.then_some(TriggerType::LastPrice)),
+            created_from: self.created_from.unwrap_or(CreatedFrom::Api),
+            sequence_id: 0,
+        };
+        order.validate()?;
+        Ok(order)
+    }
+}
+
 /// Represents a completed trade resulting from matching two orders.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Trade {
@@ -192,6 +423,12 @@ pub struct Trade {
     pub maker_order_id: Uuid,
     /// ID of the order that matched the resting order (taker).
     pub taker_order_id: Uuid,
+    /// Account that owned the maker order.
+    pub maker_account_id: Uuid,
+    /// Account that owned the taker order.
+    pub taker_account_id: Uuid,
+    /// Side of the taker order; the maker traded on the opposite side.
+    pub taker_side: Side,
     /// Quantity traded in base units. Stored as Decimal.
     pub base_amount: Decimal,
     /// Quantity traded in quote units. Stored as Decimal. Calculated as base_amount * price.
@@ -202,6 +439,48 @@ pub struct Trade {
     pub created_at: DateTime<Utc>,
 }
 
+impl Trade {
+    /// Returns the side the maker traded on.
+    pub fn maker_side(&self) -> Side {
+        match self.taker_side {
+            Side::Bid => Side::Ask,
+            Side::Ask => Side::Bid,
+        }
+    }
+
+    /// Returns the liquidity role of `order_id` in this trade, or None if it did not take part.
+    pub fn liquidity_of(&self, order_id: Uuid) -> Option<Liquidity> {
+        if order_id == self.maker_order_id {
+            Some(Liquidity::Maker)
+        } else if order_id == self.taker_order_id {
+            Some(Liquidity::Taker)
+        } else {
+            None
+        }
+    }
+}
+
+
+/// Maker-side outcome of a single match.
+/// Reported in place of a full copy of the resting order so the match loop does not clone orders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    /// ID of the resting (maker) order that was filled.
+    pub maker_order_id: Uuid,
+    /// Account that owns the maker order.
+    pub maker_account_id: Uuid,
+    /// Price at which the fill occurred (the maker's limit price).
+    pub price: Decimal,
+    /// Quantity filled in base units.
+    pub base_amount: Decimal,
+    /// Quantity filled in quote units (`base_amount * price`).
+    pub quote_amount: Decimal,
+    /// Base quantity still resting on the maker order after this fill.
+    pub maker_remaining_base: Decimal,
+    /// Status of the maker order after this fill (PartiallyFilled or Filled).
+    pub maker_status: OrderStatus,
+}
+
 
 //--------------------------------------------------------------------------------------------------
 //  Potential Errors (Initial Placeholder)
@@ -216,6 +495,27 @@ pub enum TypeError {
     /// Occurs when attempting to create an `OrderType` from an unrecognized string or value.
     #[error("Invalid order type specified: {0}")]
     InvalidOrderType(String),
+    /// Occurs when an order status change is not allowed by the order lifecycle.
+    #[error("Invalid order status transition from {from:?} to {to:?}")]
+    InvalidStatusTransition { from: OrderStatus, to: OrderStatus },
+    /// Occurs when a required field was not set on an `OrderBuilder`.
+    #[error("Missing required order field: {0}")]
+    MissingField(&'static str),
+    /// Occurs when an order quantity is zero or negative.
+    #[error("Order amount must be positive, got {0}")]
+    NonPositiveAmount(Decimal),
+    /// Occurs when a limit or trigger price is zero or negative.
+    #[error("Order price must be positive, got {0}")]
+    NonPositivePrice(Decimal),
+    /// Occurs when a Limit/StopLimit order has no limit price.
+    #[error("{0:?} order requires a limit price")]
+    MissingLimitPrice(OrderType),
+    /// Occurs when a Stop/StopLimit order has no trigger price.
+    #[error("{0:?} order requires a trigger price")]
+    MissingTriggerPrice(OrderType),
+    /// Occurs when an order would expire at or before its creation.
+    #[error("Order expiry {0} is not in the future")]
+    ExpiryNotInFuture(DateTime<Utc>),
     // Add more specific type errors as needed
 }
 
@@ -228,6 +528,8 @@ pub enum TypeError {
 // | test_order_creation        | Verify basic Order struct instantiation.          |
 // | test_trade_creation        | Verify basic Trade struct instantiation.          |
 // | test_enum_derives          | Check basic enum functionality (clone, copy, eq).|
+// | test_status_state_machine  | Legal and illegal OrderStatus transitions.       |
+// | test_order_builder         | Builder defaults and validation errors.          |
 //--------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -274,6 +576,9 @@ mod tests {
             instrument_id: Uuid::new_v4(),
             maker_order_id: Uuid::new_v4(),
             taker_order_id: Uuid::new_v4(),
+            maker_account_id: Uuid::new_v4(),
+            taker_account_id: Uuid::new_v4(),
+            taker_side: Side::Ask,
             base_amount: dec!(0.5),
             quote_amount: dec!(25000.25),
             price: dec!(50000.50),
@@ -281,9 +586,14 @@ mod tests {
         };
         assert_eq!(trade.base_amount, dec!(0.5));
         assert_eq!(trade.price, dec!(50000.50));
+        assert_eq!(trade.maker_side(), Side::Bid);
+        assert_eq!(trade.liquidity_of(trade.maker_order_id), Some(Liquidity::Maker));
+        assert_eq!(trade.liquidity_of(trade.taker_order_id), Some(Liquidity::Taker));
+        assert_eq!(trade.liquidity_of(Uuid::new_v4()), None);
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)] // Exercises the derived Clone impls explicitly
     fn test_enum_derives() {
         // Test Side enum
         let bid_side = Side::Bid;
@@ -319,6 +629,7 @@ mod tests {
         let copied_trigger = last_price;
         assert_eq!(last_price, cloned_trigger);
         assert_eq!(last_price, copied_trigger);
+        assert_ne!(TriggerType::MarkPrice, TriggerType::IndexPrice);
 
         // Test CreatedFrom enum
         let api = CreatedFrom::Api;
@@ -350,6 +661,82 @@ mod tests {
         assert_eq!(invalid_side, cloned_side);
     }
 
+    #[test]
+    fn test_status_state_machine() {
+        use OrderStatus::*;
+
+        assert_eq!(WaitingTrigger.transition(New), Ok(New));
+        assert_eq!(New.transition(PartiallyFilled), Ok(PartiallyFilled));
+        assert_eq!(PartiallyFilled.transition(PartiallyFilled), Ok(PartiallyFilled));
+        assert_eq!(PartiallyFilled.transition(Filled), Ok(Filled));
+        assert_eq!(New.cancel(), Ok(Cancelled));
+        assert_eq!(WaitingTrigger.cancel(), Ok(Cancelled));
+        assert_eq!(PartiallyFilled.cancel(), Ok(PartiallyFilledCancelled));
+        assert_eq!(New.transition(Expired), Ok(Expired));
+        assert_eq!(PartiallyFilled.transition(Expired), Ok(Expired));
+
+        // Fills cannot be undone and terminal statuses are final
+        assert!(!PartiallyFilled.can_transition_to(New));
+        assert!(!PartiallyFilled.can_transition_to(Cancelled));
+        assert!(!WaitingTrigger.can_transition_to(Filled));
+        assert!(!WaitingTrigger.can_transition_to(Expired));
+        for terminal in [Filled, Cancelled, PartiallyFilledCancelled, Expired] {
+            assert!(terminal.is_terminal());
+            assert!(terminal.cancel().is_err());
+            assert!(!terminal.can_transition_to(Expired));
+        }
+        assert_eq!(
+            Filled.transition(New),
+            Err(TypeError::InvalidStatusTransition { from: Filled, to: New })
+        );
+        assert_eq!(
+            Cancelled.transition(New).unwrap_err().to_string(),
+            "Invalid order status transition from Cancelled to New"
+        );
+    }
+
+    #[test]
+    fn test_order_builder() {
+        let account_id = Uuid::new_v4();
+        let instrument_id = Uuid::new_v4();
+        let base = || Order::builder().account_id(account_id).instrument_id(instrument_id).side(Side::Bid);
+
+        let limit = base().order_type(OrderType::Limit).limit_price(dec!(100)).base_amount(dec!(2)).ext_id("c-1").build().unwrap();
+        assert_eq!(limit.status, OrderStatus::New);
+        assert_eq!(limit.remaining_base, dec!(2));
+        assert_eq!(limit.remaining_quote, dec!(200));
+        assert_eq!(limit.ext_id.as_deref(), Some("c-1"));
+        assert_eq!(limit.trigger_by, None);
+        assert!(limit.expiration_date > limit.created_at);
+
+        let stop = base().order_type(OrderType::Stop).trigger_price(dec!(105)).base_amount(dec!(1)).build().unwrap();
+        assert_eq!(stop.status, OrderStatus::WaitingTrigger);
+        assert_eq!(stop.trigger_by, Some(TriggerType::LastPrice));
+
+        assert_eq!(
+            Order::builder().order_type(OrderType::Market).base_amount(dec!(1)).build(),
+            Err(TypeError::MissingField("account_id"))
+        );
+        assert_eq!(
+            base().order_type(OrderType::Limit).base_amount(dec!(1)).build(),
+            Err(TypeError::MissingLimitPrice(OrderType::Limit))
+        );
+        assert_eq!(
+            base().order_type(OrderType::StopLimit).limit_price(dec!(100)).base_amount(dec!(1)).build(),
+            Err(TypeError::MissingTriggerPrice(OrderType::StopLimit))
+        );
+        assert_eq!(
+            base().order_type(OrderType::Market).base_amount(dec!(0)).build(),
+            Err(TypeError::NonPositiveAmount(dec!(0)))
+        );
+        assert_eq!(
+            base().order_type(OrderType::Limit).limit_price(dec!(-1)).base_amount(dec!(1)).build(),
+            Err(TypeError::NonPositivePrice(dec!(-1)))
+        );
+        let expired = base().order_type(OrderType::Market).base_amount(dec!(1)).expiration_date(Utc::now() - chrono::Duration::seconds(1));
+        assert!(matches!(expired.build(), Err(TypeError::ExpiryNotInFuture(_))));
+    }
+
     #[test]
     fn test_order_with_different_types() {
         let now = Utc::now();
@@ -0,0 +1,135 @@
+//--------------------------------------------------------------------------------------------------
+// ALLOCATION HARNESS
+//--------------------------------------------------------------------------------------------------
+// Counts heap allocations made by the matching hot path, so changes that add allocations to
+// process_order/cancel_order/amend_order fail loudly instead of silently regressing latency.
+// Lives in its own integration test binary because it installs a global allocator.
+//
+// | Name                                                  | Description                          |
+// |-------------------------------------------------------|--------------------------------------|
+// | test_no_allocation_when_cancelling_resting_order      | Cancel path is allocation-free       |
+// | test_no_allocation_when_reducing_order_in_place       | In-place amend is allocation-free    |
+// | test_only_result_buffers_allocated_when_matching      | Match allocates only its result Vecs |
+//--------------------------------------------------------------------------------------------------
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ultimate_matching::matching_engine::MatchingEngine;
+use ultimate_matching::types::{Order, OrderType, Side, TimeInForce};
+use uuid::Uuid;
+
+/// Allocations a fully matching taker may make: one buffer each for `trades` and `fills`.
+const MATCH_ALLOCATION_BUDGET: usize = 2;
+
+thread_local! {
+    static IS_COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Forwards to the system allocator, counting allocations made on a thread that is measuring.
+/// Counting is per thread so allocations from concurrently running tests are not attributed.
+struct CountingAllocator;
+
+// SAFETY: every call is forwarded unchanged to `System`; the only extra work is bumping a
+// const-initialized thread-local counter, which never allocates.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        // SAFETY: forwarded with the caller's layout, upholding the same contract.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: `ptr` was allocated by `System` through this allocator with `layout`.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation();
+        // SAFETY: `ptr` was allocated by `System` through this allocator with `layout`.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn record_allocation() {
+    // try_with: the allocator can be called while thread-locals are being torn down
+    let _ = IS_COUNTING.try_with(|is_counting| {
+        if is_counting.get() {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        }
+    });
+}
+
+/// Runs `f` and returns its output along with the number of allocations it made on this thread.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    ALLOCATIONS.with(|count| count.set(0));
+    IS_COUNTING.with(|is_counting| is_counting.set(true));
+    let output = f();
+    IS_COUNTING.with(|is_counting| is_counting.set(false));
+    (output, ALLOCATIONS.with(Cell::get))
+}
+
+/// Orders without an `ext_id`, so cloning one never touches the heap.
+fn create_test_order(side: Side, price: Decimal, quantity: Decimal, instrument_id: Uuid) -> Order {
+    Order::builder()
+        .account_id(Uuid::new_v4())
+        .instrument_id(instrument_id)
+        .side(side)
+        .order_type(OrderType::Limit)
+        .limit_price(price)
+        .base_amount(quantity)
+        .build()
+        .expect("test order is valid")
+}
+
+#[test]
+fn test_no_allocation_when_cancelling_resting_order() {
+    let instrument_id = Uuid::new_v4();
+    let mut engine = MatchingEngine::new(instrument_id);
+    let order = create_test_order(Side::Bid, dec!(100.0), dec!(1.0), instrument_id);
+    let order_id = order.id;
+    engine.process_order(order, TimeInForce::GTC).unwrap();
+
+    let (cancelled, allocations) = count_allocations(|| engine.cancel_order(order_id));
+    assert!(cancelled.is_ok());
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_no_allocation_when_reducing_order_in_place() {
+    let instrument_id = Uuid::new_v4();
+    let mut engine = MatchingEngine::new(instrument_id);
+    let order = create_test_order(Side::Ask, dec!(100.0), dec!(2.0), instrument_id);
+    let order_id = order.id;
+    engine.process_order(order, TimeInForce::GTC).unwrap();
+
+    let (amended, allocations) = count_allocations(|| engine.amend_order(order_id, None, Some(dec!(1.0))));
+    assert!(amended.is_ok());
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_only_result_buffers_allocated_when_matching() {
+    let instrument_id = Uuid::new_v4();
+    let mut engine = MatchingEngine::new(instrument_id);
+    for _ in 0..3 {
+        let maker = create_test_order(Side::Ask, dec!(100.0), dec!(1.0), instrument_id);
+        engine.process_order(maker, TimeInForce::GTC).unwrap();
+    }
+    let taker = create_test_order(Side::Bid, dec!(100.0), dec!(3.0), instrument_id);
+
+    let (result, allocations) = count_allocations(|| engine.process_order(taker, TimeInForce::IOC));
+    assert_eq!(result.map(|r| r.trades.len()), Ok(3));
+    assert!(
+        allocations <= MATCH_ALLOCATION_BUDGET,
+        "matching made {} allocations, budget is {}",
+        allocations,
+        MATCH_ALLOCATION_BUDGET
+    );
+}
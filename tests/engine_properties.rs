@@ -0,0 +1,238 @@
+//--------------------------------------------------------------------------------------------------
+// PROPERTY TESTS
+//--------------------------------------------------------------------------------------------------
+// Drives the matching engine with arbitrary streams of limit, market, cancel and amend commands and
+// checks book and trade invariants after every step. Failing streams are shrunk by proptest to a
+// minimal reproduction; set PROPTEST_CASES to run more cases locally.
+//
+// | Invariant                 | Description                                                     |
+// |---------------------------|-----------------------------------------------------------------|
+// | no negative amounts       | Resting orders have positive remaining and non-negative fills   |
+// | uncrossed book            | Best bid is strictly below best ask after every command         |
+// | level volume              | Each level's volume equals the sum of its orders' remaining     |
+// | trade consistency         | quote = price * base, price is the maker's, within taker limit  |
+// | GTX never takes           | GTX orders rest or cancel, never trade, including on amend      |
+// | conservation              | Every order's filled base and quote equal the base and quote    |
+// |                           | traded against it, and filled + remaining never exceeds the     |
+// |                           | original amount                                                 |
+//--------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+use rust_decimal::Decimal;
+use ultimate_matching::matching_engine::{MatchResult, MatchingEngine};
+use ultimate_matching::types::{Order, OrderType, Side, TimeInForce, Trade};
+use uuid::Uuid;
+
+/// A single generated command.
+#[derive(Debug, Clone)]
+enum Command {
+    Limit { side: Side, price_ticks: u32, quantity: u32, time_in_force: TimeInForce },
+    Market { side: Side, quantity: u32 },
+    /// Cancels the n-th submitted order, modulo the number submitted so far
+    Cancel(usize),
+    /// Amends the n-th submitted order, modulo the number submitted so far
+    Amend { n: usize, price_ticks: Option<u32>, quantity: Option<u32> },
+}
+
+fn side_strategy() -> impl Strategy<Value = Side> {
+    prop_oneof![Just(Side::Bid), Just(Side::Ask)]
+}
+
+fn command_strategy() -> impl Strategy<Value = Command> {
+    let time_in_force = prop_oneof![3 => Just(TimeInForce::GTC), 1 => Just(TimeInForce::IOC), 1 => Just(TimeInForce::GTX)];
+    prop_oneof![
+        6 => (side_strategy(), 90u32..=110, 1u32..=10, time_in_force).prop_map(|(side, price_ticks, quantity, time_in_force)| {
+            Command::Limit { side, price_ticks, quantity, time_in_force }
+        }),
+        1 => (side_strategy(), 1u32..=15).prop_map(|(side, quantity)| Command::Market { side, quantity }),
+        2 => any::<usize>().prop_map(Command::Cancel),
+        2 => (any::<usize>(), proptest::option::of(90u32..=110), proptest::option::of(1u32..=10)).prop_map(
+            |(n, price_ticks, quantity)| Command::Amend { n, price_ticks, quantity }
+        ),
+    ]
+}
+
+fn create_order(side: Side, order_type: OrderType, price: Option<Decimal>, quantity: Decimal, instrument_id: Uuid) -> Order {
+    let mut builder = Order::builder()
+        .account_id(Uuid::new_v4())
+        .instrument_id(instrument_id)
+        .side(side)
+        .order_type(order_type)
+        .base_amount(quantity);
+    if let Some(price) = price {
+        builder = builder.limit_price(price);
+    }
+    builder.build().expect("generated orders are valid")
+}
+
+/// Base and quote traded against each order so far, as seen from the trade stream.
+#[derive(Default)]
+struct TradeTally {
+    traded_base: HashMap<Uuid, Decimal>,
+    traded_quote: HashMap<Uuid, Decimal>,
+}
+
+impl TradeTally {
+    fn traded(&self, order_id: Uuid) -> Decimal {
+        self.traded_base.get(&order_id).copied().unwrap_or_default()
+    }
+
+    fn traded_quote(&self, order_id: Uuid) -> Decimal {
+        self.traded_quote.get(&order_id).copied().unwrap_or_default()
+    }
+
+    fn record(&mut self, trade: &Trade) {
+        for order_id in [trade.maker_order_id, trade.taker_order_id] {
+            *self.traded_base.entry(order_id).or_default() += trade.base_amount;
+            *self.traded_quote.entry(order_id).or_default() += trade.quote_amount;
+        }
+    }
+}
+
+/// Checks every resting order and level against the book invariants.
+fn check_book(engine: &MatchingEngine, tally: &TradeTally) -> Result<(), TestCaseError> {
+    let book = engine.order_book();
+    if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+        prop_assert!(bid < ask, "crossed book: bid {} >= ask {}", bid, ask);
+    }
+
+    let depth = book.depth(usize::MAX);
+    for (side, levels) in [(Side::Bid, &depth.bids), (Side::Ask, &depth.asks)] {
+        for level in levels {
+            let orders = book.get_orders_at_price(side, level.price).expect("depth level without orders");
+            prop_assert!(!orders.is_empty(), "empty level left at {}", level.price);
+            prop_assert_eq!(orders.len(), level.order_count);
+
+            let mut volume = Decimal::ZERO;
+            for order in orders {
+                prop_assert!(order.remaining_base > Decimal::ZERO, "resting order {} has no remaining", order.id);
+                prop_assert!(order.filled_base >= Decimal::ZERO);
+                prop_assert_eq!(order.filled_base + order.remaining_base, order.base_amount);
+                prop_assert_eq!(order.filled_base, tally.traded(order.id));
+                prop_assert_eq!(order.filled_quote, tally.traded_quote(order.id));
+                volume += order.remaining_base;
+            }
+            prop_assert_eq!(volume, level.volume, "level volume drifted at {}", level.price);
+        }
+    }
+    Ok(())
+}
+
+/// Checks the trades and fills of a result produced by `taker_id` acting as the taker, and books
+/// the trades into `tally`.
+fn check_result(
+    result: &MatchResult,
+    taker_id: Uuid,
+    taker_side: Side,
+    taker_limit: Option<Decimal>,
+    resting_prices: &HashMap<Uuid, Decimal>,
+    tally: &mut TradeTally,
+) -> Result<(), TestCaseError> {
+    for trade in &result.trades {
+        prop_assert!(trade.base_amount > Decimal::ZERO);
+        prop_assert_eq!(trade.quote_amount, trade.price * trade.base_amount);
+        prop_assert_eq!(trade.taker_order_id, taker_id);
+        prop_assert_eq!(trade.taker_side, taker_side);
+        prop_assert_eq!(Some(&trade.price), resting_prices.get(&trade.maker_order_id));
+        match (taker_side, taker_limit) {
+            (Side::Bid, Some(limit)) => prop_assert!(trade.price <= limit),
+            (Side::Ask, Some(limit)) => prop_assert!(trade.price >= limit),
+            (_, None) => {}
+        }
+        tally.record(trade);
+    }
+
+    let taker_traded = tally.traded(taker_id);
+    if let Some(processed) = &result.processed_order {
+        prop_assert!(taker_traded <= processed.base_amount, "taker overfilled: {} > {}", taker_traded, processed.base_amount);
+        prop_assert_eq!(processed.filled_base, taker_traded);
+        prop_assert_eq!(processed.filled_quote, tally.traded_quote(taker_id));
+        prop_assert!(processed.remaining_base >= Decimal::ZERO);
+        prop_assert!(processed.filled_base + processed.remaining_base <= processed.base_amount);
+    }
+    for fill in &result.fills {
+        prop_assert!(fill.maker_remaining_base >= Decimal::ZERO);
+    }
+    Ok(())
+}
+
+fn run_commands(commands: Vec<Command>) -> Result<(), TestCaseError> {
+    let instrument_id = Uuid::new_v4();
+    let mut engine = MatchingEngine::new(instrument_id);
+    let mut tally = TradeTally::default();
+    let mut submitted: Vec<(Uuid, Side, TimeInForce)> = Vec::new();
+    // Limit price of every order that may rest, to check trades execute at the maker's price
+    let mut resting_prices: HashMap<Uuid, Decimal> = HashMap::new();
+
+    for command in commands {
+        let order = match command {
+            Command::Limit { side, price_ticks, quantity, time_in_force } => {
+                (create_order(side, OrderType::Limit, Some(Decimal::from(price_ticks)), Decimal::from(quantity), instrument_id), time_in_force)
+            }
+            Command::Market { side, quantity } => {
+                (create_order(side, OrderType::Market, None, Decimal::from(quantity), instrument_id), TimeInForce::IOC)
+            }
+            Command::Cancel(n) => {
+                if !submitted.is_empty() {
+                    // Cancelling filled or unknown orders is expected to fail; the book must not change
+                    let _ = engine.cancel_order(submitted[n % submitted.len()].0);
+                }
+                check_book(&engine, &tally)?;
+                continue;
+            }
+            Command::Amend { n, price_ticks, quantity } => {
+                if !submitted.is_empty() {
+                    let (order_id, side, time_in_force) = submitted[n % submitted.len()];
+                    let new_price = price_ticks.map(Decimal::from);
+                    // Amending orders that are gone or below their filled amount is expected to fail
+                    if let Ok(result) = engine.amend_order(order_id, new_price, quantity.map(Decimal::from)) {
+                        let amended_limit = result.processed_order.as_ref().and_then(|order| order.limit_price);
+                        if time_in_force == TimeInForce::GTX {
+                            prop_assert!(result.trades.is_empty(), "GTX order took liquidity on amend");
+                        }
+                        check_result(&result, order_id, side, amended_limit, &resting_prices, &mut tally)?;
+                        if let Some(price) = amended_limit {
+                            resting_prices.insert(order_id, price);
+                        }
+                    }
+                }
+                check_book(&engine, &tally)?;
+                continue;
+            }
+        };
+
+        let (order, time_in_force) = order;
+        let (taker_id, taker_side, taker_limit) = (order.id, order.side, order.limit_price);
+        submitted.push((taker_id, taker_side, time_in_force));
+
+        let result = match engine.process_order(order, time_in_force) {
+            Ok(result) => result,
+            // Market orders into an empty side are rejected outright
+            Err(_) => {
+                check_book(&engine, &tally)?;
+                continue;
+            }
+        };
+
+        if time_in_force == TimeInForce::GTX {
+            prop_assert!(result.trades.is_empty(), "GTX order took liquidity");
+        }
+        check_result(&result, taker_id, taker_side, taker_limit, &resting_prices, &mut tally)?;
+        // Recorded after matching: an order never trades against itself
+        if let Some(price) = result.processed_order.as_ref().and_then(|order| order.limit_price) {
+            resting_prices.insert(taker_id, price);
+        }
+
+        check_book(&engine, &tally)?;
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_invariants_hold_for_arbitrary_order_streams(commands in prop::collection::vec(command_strategy(), 1..200)) {
+        run_commands(commands)?;
+    }
+}